@@ -2,7 +2,7 @@
 //!
 //! Run with: cargo run --example basic_movegen
 
-use movegen::{Board, Square, Piece, Color};
+use movegen::{Board, Piece};
 
 fn main() {
     println!("=== Ferrum Movegen: Basic Move Generation ===\n");
@@ -25,14 +25,11 @@ fn main() {
     let mut pawn_moves = Vec::new();
     let mut knight_moves = Vec::new();
     
-    for mv in moves.iter() {
-        let from = mv.from();
-        if let Some((piece, _color)) = board.piece_at(from) {
-            match piece {
-                Piece::Pawn => pawn_moves.push(mv.to_uci()),
-                Piece::Knight => knight_moves.push(mv.to_uci()),
-                _ => {}
-            }
+    for (mv, piece) in moves.iter_with_pieces(&board) {
+        match piece {
+            Piece::Pawn => pawn_moves.push(mv.to_uci()),
+            Piece::Knight => knight_moves.push(mv.to_uci()),
+            _ => {}
         }
     }
     
@@ -126,9 +126,8 @@ fn main() {
     // Is the king in check?
     let king_sq = board.king_square(board.turn());
     let enemy = !board.turn();
-    let enemy_attacks = board.attackers_to(king_sq, board.occupied()) & board.color_bb(enemy);
-    
+
     println!("\n{:?} king on {:?}", board.turn(), king_sq);
-    println!("In check: {}", !enemy_attacks.is_empty());
+    println!("In check: {}", board.is_attacked(king_sq, enemy));
     println!("Checkers: {:?}", board.checkers());
 }
@@ -4,7 +4,7 @@
 //!
 //! Run with: cargo run --example fen_parsing
 
-use movegen::{Board, Color, Piece, Square, CastleRights};
+use movegen::{Board, Color};
 
 fn main() {
     println!("=== Ferrum Movegen: FEN Parsing ===\n");
@@ -39,11 +39,8 @@ fn main() {
     
     let board = Board::from_fen(middlegame).expect("Valid FEN");
     println!("Pieces on board:");
-    for sq_idx in 0..64 {
-        let sq = Square::from_index(sq_idx).unwrap();
-        if let Some((piece, color)) = board.piece_at(sq) {
-            println!("  {:?}: {:?} {:?}", sq, color, piece);
-        }
+    for (sq, piece, color) in board.piece_iter() {
+        println!("  {:?}: {:?} {:?}", sq, color, piece);
     }
     println!();
 
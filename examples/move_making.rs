@@ -4,7 +4,7 @@
 //!
 //! Run with: cargo run --example move_making
 
-use movegen::{Board, Move, MoveFlag, Square};
+use movegen::Board;
 
 fn main() {
     println!("=== Ferrum Movegen: Making Moves ===\n");
@@ -19,7 +19,7 @@ fn main() {
     println!("It's simpler and often faster for small search depths.\n");
 
     // Play 1. e4
-    let e2e4 = find_move(&board, "e2e4").expect("e2e4 should be legal");
+    let e2e4 = board.parse_uci("e2e4").expect("e2e4 should be legal");
     let board_after_e4 = board.make_move_new(e2e4);
     println!("After 1. e4: {}", board_after_e4.to_fen());
     
@@ -31,7 +31,7 @@ fn main() {
     let mut current = board_after_e4;
     
     for uci in moves {
-        let mv = find_move(&current, uci).expect("Move should be legal");
+        let mv = current.parse_uci(uci).expect("Move should be legal");
         current = current.make_move_new(mv);
     }
     println!("Ruy Lopez after 3. Bb5: {}", current.to_fen());
@@ -46,7 +46,7 @@ fn main() {
     println!("Initial hash: {:016x}", initial_hash);
 
     // Make a move
-    let e2e4 = find_move(&board, "e2e4").expect("e2e4 should be legal");
+    let e2e4 = board.parse_uci("e2e4").expect("e2e4 should be legal");
     let undo = board.make_move(e2e4);
     println!("After e2e4 hash: {:016x}", board.hash());
 
@@ -70,8 +70,8 @@ fn main() {
     let castling_pos = "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1";
     let board = Board::from_fen(castling_pos).unwrap();
     
-    let kingside = find_move(&board, "e1g1");
-    let queenside = find_move(&board, "e1c1");
+    let kingside = board.parse_uci("e1g1");
+    let queenside = board.parse_uci("e1c1");
     
     println!("Castling position: {}", castling_pos);
     println!("Kingside castle available: {}", kingside.is_some());
@@ -89,7 +89,7 @@ fn main() {
     println!("\nEn passant position: {}", ep_pos);
     println!("En passant square: {:?}", board.ep_square());
     
-    let ep_capture = find_move(&board, "e5f6");
+    let ep_capture = board.parse_uci("e5f6");
     if let Some(mv) = ep_capture {
         println!("En passant capture available: e5f6");
         let after = board.make_move_new(mv);
@@ -119,13 +119,6 @@ fn main() {
     }
 }
 
-/// Find a legal move by its UCI string.
-fn find_move(board: &Board, uci: &str) -> Option<Move> {
-    board.generate_moves()
-        .iter()
-        .find(|m| m.to_uci() == uci)
-}
-
 /// Count positions at a given depth (minimax-style traversal).
 fn count_positions(board: &Board, depth: u32) -> u64 {
     if depth == 0 {
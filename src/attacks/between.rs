@@ -1,7 +1,7 @@
 //! Line and between bitboards for pin/check detection.
 
 use crate::bitboard::Bitboard;
-use crate::types::Square;
+use crate::types::{Direction, Square};
 
 /// Precomputed line tables [square1][square2].
 /// Contains the full line through both squares if they're on a rank, file, or diagonal.
@@ -25,6 +25,44 @@ pub fn between(sq1: Square, sq2: Square) -> Bitboard {
     BETWEEN[sq1.index() as usize][sq2.index() as usize]
 }
 
+/// Get the squares between two squares including both endpoints.
+/// Returns empty bitboard if squares are not on the same rank, file, or diagonal.
+#[inline(always)]
+pub fn between_inclusive(sq1: Square, sq2: Square) -> Bitboard {
+    if line(sq1, sq2).is_empty() {
+        return Bitboard::EMPTY;
+    }
+    between(sq1, sq2) | Bitboard::from_square(sq1) | Bitboard::from_square(sq2)
+}
+
+/// Get the direction from `from` toward `to`.
+/// Returns `None` if the squares are equal or not on the same rank, file, or diagonal.
+pub const fn ray_between(from: Square, to: Square) -> Option<Direction> {
+    let df = (to.index() & 7) as i8 - (from.index() & 7) as i8;
+    let dr = (to.index() >> 3) as i8 - (from.index() >> 3) as i8;
+    if df != 0 && dr != 0 && df.abs() != dr.abs() {
+        return None;
+    }
+    match (df.signum(), dr.signum()) {
+        (0, 1) => Some(Direction::North),
+        (0, -1) => Some(Direction::South),
+        (1, 0) => Some(Direction::East),
+        (-1, 0) => Some(Direction::West),
+        (1, 1) => Some(Direction::NorthEast),
+        (-1, 1) => Some(Direction::NorthWest),
+        (1, -1) => Some(Direction::SouthEast),
+        (-1, -1) => Some(Direction::SouthWest),
+        _ => None,
+    }
+}
+
+/// Check whether three squares lie on a common rank, file, or diagonal.
+/// Returns false if `a` and `b` are the same square.
+#[inline(always)]
+pub fn aligned(a: Square, b: Square, c: Square) -> bool {
+    line(a, b).contains(c)
+}
+
 /// Generate line tables at compile time.
 const fn generate_lines() -> [[Bitboard; 64]; 64] {
     let mut lines = [[Bitboard::EMPTY; 64]; 64];
@@ -126,10 +164,8 @@ const fn generate_between() -> [[Bitboard; 64]; 64] {
                 true
             } else if dr == 0 && df != 0 {
                 true
-            } else if df.abs() == dr.abs() {
-                true
             } else {
-                false
+                df.abs() == dr.abs()
             };
             
             if on_line {
@@ -160,6 +196,49 @@ const fn generate_between() -> [[Bitboard; 64]; 64] {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_aligned() {
+        assert!(aligned(Square::A1, Square::C3, Square::H8));
+        assert!(aligned(Square::E1, Square::E8, Square::E4));
+        assert!(aligned(Square::B4, Square::G4, Square::A4));
+        assert!(!aligned(Square::A1, Square::C3, Square::C4));
+        assert!(!aligned(Square::E1, Square::F3, Square::G5));
+        assert!(!aligned(Square::E4, Square::E4, Square::E5));
+    }
+
+    #[test]
+    fn test_between_inclusive() {
+        let b = between_inclusive(Square::C3, Square::F6);
+        assert_eq!(b.count(), 4);
+        assert!(b.contains(Square::C3) && b.contains(Square::F6));
+        assert_eq!(b, between_inclusive(Square::F6, Square::C3));
+        assert_eq!(between_inclusive(Square::E1, Square::E2).count(), 2);
+        assert!(between_inclusive(Square::A1, Square::B3).is_empty());
+        assert!(between_inclusive(Square::D4, Square::D4).is_empty());
+    }
+
+    #[test]
+    fn test_ray_between() {
+        assert_eq!(ray_between(Square::E1, Square::E8), Some(Direction::North));
+        assert_eq!(ray_between(Square::H8, Square::A1), Some(Direction::SouthWest));
+        assert_eq!(ray_between(Square::A8, Square::H1), Some(Direction::SouthEast));
+        assert_eq!(ray_between(Square::D4, Square::A4), Some(Direction::West));
+        assert_eq!(ray_between(Square::A1, Square::B3), None);
+        assert_eq!(ray_between(Square::D4, Square::D4), None);
+
+        // Stepping along the ray walks the between squares
+        for (from, to) in [(Square::B2, Square::G7), (Square::H3, Square::C3), (Square::F1, Square::A6)] {
+            let dir = ray_between(from, to).unwrap();
+            let mut walked = Bitboard::EMPTY;
+            let mut sq = from;
+            while sq != to {
+                sq = Square::from_index((sq.index() as i8 + dir.delta()) as u8).unwrap();
+                walked |= Bitboard::from_square(sq);
+            }
+            assert_eq!(walked | Bitboard::from_square(from), between_inclusive(from, to));
+        }
+    }
+
     #[test]
     fn test_between_file() {
         // Between a1 and a8
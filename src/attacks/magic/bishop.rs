@@ -1,6 +1,9 @@
 //! Bishop magic bitboards.
 
-use super::{Magic, bishop_mask, bishop_attacks_slow};
+use super::{Magic, bishop_mask};
+#[cfg(not(feature = "no-tables"))]
+use super::bishop_attacks_slow;
+#[cfg(not(feature = "no-tables"))]
 use crate::bitboard::Bitboard;
 use crate::types::Square;
 
@@ -28,13 +31,23 @@ const BISHOP_MAGIC_NUMBERS: [u64; 64] = [
 pub static BISHOP_MAGICS: [Magic; 64] = init_bishop_magics();
 
 /// Precomputed bishop attack table.
+#[cfg(not(any(feature = "runtime-magics", feature = "no-tables")))]
 static BISHOP_ATTACKS: [Bitboard; 5248] = init_bishop_attacks();
 
+/// Bishop attack table, built on first use.
+#[cfg(all(feature = "runtime-magics", not(feature = "no-tables")))]
+static BISHOP_ATTACKS: std::sync::OnceLock<Box<[Bitboard]>> = std::sync::OnceLock::new();
+
 /// Get bishop attacks for a given square and occupancy.
+#[cfg(not(feature = "no-tables"))]
 #[inline(always)]
 pub fn bishop_attacks(sq: Square, occ: Bitboard) -> Bitboard {
     let magic = &BISHOP_MAGICS[sq.index() as usize];
-    BISHOP_ATTACKS[magic.index(occ)]
+    #[cfg(feature = "runtime-magics")]
+    let table = BISHOP_ATTACKS.get_or_init(|| super::build_attack_table(&BISHOP_MAGICS, 5248, bishop_attacks_slow));
+    #[cfg(not(feature = "runtime-magics"))]
+    let table = &BISHOP_ATTACKS;
+    table[magic.index(occ)]
 }
 
 /// Initialize bishop magic entries.
@@ -65,6 +78,7 @@ const fn init_bishop_magics() -> [Magic; 64] {
 }
 
 /// Initialize bishop attack table.
+#[cfg(not(any(feature = "runtime-magics", feature = "no-tables")))]
 const fn init_bishop_attacks() -> [Bitboard; 5248] {
     let mut attacks = [Bitboard::EMPTY; 5248];
     
@@ -108,7 +122,7 @@ const fn init_bishop_attacks() -> [Bitboard; 5248] {
     attacks
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "no-tables")))]
 mod tests {
     use super::*;
 
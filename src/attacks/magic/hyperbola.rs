@@ -0,0 +1,56 @@
+//! Table-free slider attacks using hyperbola quintessence.
+//!
+//! Only the `RAYS` table is needed, so this trades speed for memory.
+
+use crate::attacks::rays::{ray, ray_attacks};
+use crate::bitboard::Bitboard;
+use crate::types::{Direction, Square};
+
+/// Attacks along a line through `sq` that `flip_vertical` reverses (file or diagonal).
+#[inline(always)]
+fn line_attacks(sq: Square, occ: Bitboard, mask: Bitboard) -> Bitboard {
+    let o = occ.0 & mask.0;
+    let r = 1u64 << sq.index();
+    let forward = o.wrapping_sub(r.wrapping_mul(2));
+    let reverse = o.swap_bytes().wrapping_sub(r.swap_bytes().wrapping_mul(2)).swap_bytes();
+    Bitboard((forward ^ reverse) & mask.0)
+}
+
+/// Attacks along the rank, where byte swapping does not help.
+#[inline(always)]
+fn rank_attacks(sq: Square, occ: Bitboard) -> Bitboard {
+    ray_attacks(Direction::East, sq, occ) | ray_attacks(Direction::West, sq, occ)
+}
+
+/// Get rook attacks for a given square and occupancy.
+#[inline]
+pub fn rook_attacks(sq: Square, occ: Bitboard) -> Bitboard {
+    let file = ray(Direction::North, sq) | ray(Direction::South, sq);
+    line_attacks(sq, occ, file) | rank_attacks(sq, occ)
+}
+
+/// Get bishop attacks for a given square and occupancy.
+#[inline]
+pub fn bishop_attacks(sq: Square, occ: Bitboard) -> Bitboard {
+    let diagonal = ray(Direction::NorthEast, sq) | ray(Direction::SouthWest, sq);
+    let anti_diagonal = ray(Direction::NorthWest, sq) | ray(Direction::SouthEast, sq);
+    line_attacks(sq, occ, diagonal) | line_attacks(sq, occ, anti_diagonal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{bishop_attacks_slow, bishop_mask, enumerate_occupancies, rook_attacks_slow, rook_mask};
+
+    #[test]
+    fn test_matches_slow() {
+        for sq in Bitboard::UNIVERSE {
+            for occ in enumerate_occupancies(rook_mask(sq)) {
+                assert_eq!(rook_attacks(sq, occ), rook_attacks_slow(sq, occ));
+            }
+            for occ in enumerate_occupancies(bishop_mask(sq)) {
+                assert_eq!(bishop_attacks(sq, occ), bishop_attacks_slow(sq, occ));
+            }
+        }
+    }
+}
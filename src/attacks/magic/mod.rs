@@ -5,22 +5,35 @@
 mod rook;
 mod bishop;
 
-#[cfg(feature = "pext")]
+#[cfg(all(feature = "pext", not(feature = "no-tables")))]
 mod pext;
 
+#[cfg(feature = "no-tables")]
+mod hyperbola;
+
+#[cfg(feature = "std")]
+mod search;
+
+// Without tables, compute attacks on the fly
+#[cfg(feature = "no-tables")]
+pub use hyperbola::{rook_attacks, bishop_attacks};
+
 // When PEXT is enabled, use PEXT-based attacks
-#[cfg(feature = "pext")]
+#[cfg(all(feature = "pext", not(feature = "no-tables")))]
 pub use pext::{rook_attacks_pext as rook_attacks, bishop_attacks_pext as bishop_attacks};
 
 // Otherwise, use magic bitboard attacks
-#[cfg(not(feature = "pext"))]
+#[cfg(not(any(feature = "pext", feature = "no-tables")))]
 pub use rook::rook_attacks;
-#[cfg(not(feature = "pext"))]
+#[cfg(not(any(feature = "pext", feature = "no-tables")))]
 pub use bishop::bishop_attacks;
 
 pub use rook::ROOK_MAGICS;
 pub use bishop::BISHOP_MAGICS;
 
+#[cfg(feature = "std")]
+pub use search::{find_magic, magic_is_valid, verify_magics};
+
 use crate::bitboard::Bitboard;
 use crate::types::Square;
 
@@ -235,6 +248,19 @@ pub const fn bishop_attacks_slow(sq: Square, occ: Bitboard) -> Bitboard {
     Bitboard(attacks)
 }
 
+/// Fill a slider attack table at runtime from its magic entries.
+#[cfg(all(feature = "runtime-magics", not(feature = "no-tables")))]
+fn build_attack_table(magics: &[Magic; 64], size: usize, slow: fn(Square, Bitboard) -> Bitboard) -> Box<[Bitboard]> {
+    let mut table = vec![Bitboard::EMPTY; size].into_boxed_slice();
+    for sq in Bitboard::UNIVERSE {
+        let magic = &magics[sq.index() as usize];
+        for occ in enumerate_occupancies(magic.mask) {
+            table[magic.index(occ)] = slow(sq, occ);
+        }
+    }
+    table
+}
+
 /// Enumerate all occupancy configurations for a mask.
 #[inline]
 pub fn enumerate_occupancies(mask: Bitboard) -> impl Iterator<Item = Bitboard> {
@@ -290,4 +316,17 @@ mod tests {
         assert!(attacks.contains(Square::E6)); // Blocker included
         assert!(!attacks.contains(Square::E7)); // Blocked
     }
+
+    #[test]
+    #[cfg(not(feature = "no-tables"))]
+    fn test_tables_match_slow() {
+        for sq in Bitboard::UNIVERSE {
+            for occ in enumerate_occupancies(rook_mask(sq)) {
+                assert_eq!(rook::rook_attacks(sq, occ), rook_attacks_slow(sq, occ));
+            }
+            for occ in enumerate_occupancies(bishop_mask(sq)) {
+                assert_eq!(bishop::bishop_attacks(sq, occ), bishop_attacks_slow(sq, occ));
+            }
+        }
+    }
 }
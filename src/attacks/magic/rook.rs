@@ -1,6 +1,9 @@
 //! Rook magic bitboards.
 
-use super::{Magic, rook_mask, rook_attacks_slow};
+use super::{Magic, rook_mask};
+#[cfg(not(feature = "no-tables"))]
+use super::rook_attacks_slow;
+#[cfg(not(feature = "no-tables"))]
 use crate::bitboard::Bitboard;
 use crate::types::Square;
 
@@ -29,13 +32,23 @@ const ROOK_MAGIC_NUMBERS: [u64; 64] = [
 pub static ROOK_MAGICS: [Magic; 64] = init_rook_magics();
 
 /// Precomputed rook attack table (all occupancy configurations).
+#[cfg(not(any(feature = "runtime-magics", feature = "no-tables")))]
 static ROOK_ATTACKS: [Bitboard; 102400] = init_rook_attacks();
 
+/// Rook attack table, built on first use.
+#[cfg(all(feature = "runtime-magics", not(feature = "no-tables")))]
+static ROOK_ATTACKS: std::sync::OnceLock<Box<[Bitboard]>> = std::sync::OnceLock::new();
+
 /// Get rook attacks for a given square and occupancy.
+#[cfg(not(feature = "no-tables"))]
 #[inline(always)]
 pub fn rook_attacks(sq: Square, occ: Bitboard) -> Bitboard {
     let magic = &ROOK_MAGICS[sq.index() as usize];
-    ROOK_ATTACKS[magic.index(occ)]
+    #[cfg(feature = "runtime-magics")]
+    let table = ROOK_ATTACKS.get_or_init(|| super::build_attack_table(&ROOK_MAGICS, 102400, rook_attacks_slow));
+    #[cfg(not(feature = "runtime-magics"))]
+    let table = &ROOK_ATTACKS;
+    table[magic.index(occ)]
 }
 
 /// Initialize rook magic entries (const-compatible).
@@ -66,6 +79,7 @@ const fn init_rook_magics() -> [Magic; 64] {
 }
 
 /// Initialize rook attack table.
+#[cfg(not(any(feature = "runtime-magics", feature = "no-tables")))]
 const fn init_rook_attacks() -> [Bitboard; 102400] {
     let mut attacks = [Bitboard::EMPTY; 102400];
     
@@ -114,7 +128,7 @@ const fn init_rook_attacks() -> [Bitboard; 102400] {
     attacks
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "no-tables")))]
 mod tests {
     use super::*;
 
@@ -0,0 +1,110 @@
+//! Brute-force search and validation of magic numbers.
+
+use super::{bishop_attacks_slow, bishop_mask, enumerate_occupancies, rook_attacks_slow, rook_mask};
+use crate::bitboard::Bitboard;
+use crate::types::Square;
+
+/// Occupancy mask and reference attack function for a slider.
+fn slider(sq: Square, is_rook: bool) -> (Bitboard, fn(Square, Bitboard) -> Bitboard) {
+    if is_rook {
+        (rook_mask(sq), rook_attacks_slow)
+    } else {
+        (bishop_mask(sq), bishop_attacks_slow)
+    }
+}
+
+/// Check that `magic` with `shift` maps every relevant occupancy of `sq`
+/// to a slot without a destructive collision.
+///
+/// A shift that indexes with more bits than the square has relevant
+/// occupancy bits is rejected, which also bounds the scratch table.
+pub fn magic_is_valid(sq: Square, is_rook: bool, magic: u64, shift: u8) -> bool {
+    let (mask, attacks) = slider(sq, is_rook);
+    if shift >= 64 || 64 - shift as u32 > mask.count() {
+        return false;
+    }
+    let mut table: Vec<Option<Bitboard>> = vec![None; 1 << (64 - shift)];
+    for occ in enumerate_occupancies(mask) {
+        let index = (occ.0.wrapping_mul(magic) >> shift) as usize;
+        let attack = attacks(sq, occ);
+        match table[index] {
+            None => table[index] = Some(attack),
+            Some(existing) if existing == attack => {}
+            Some(_) => return false,
+        }
+    }
+    true
+}
+
+/// Find a magic multiplier for `sq`, returning it with its shift (64 - relevant bits).
+///
+/// Candidates are sparse random numbers from an xorshift generator seeded
+/// with `rng_seed`, so the same seed always finds the same magic.
+pub fn find_magic(sq: Square, is_rook: bool, rng_seed: u64) -> (u64, u8) {
+    let (mask, _) = slider(sq, is_rook);
+    let shift = 64 - mask.count() as u8;
+    let mut state = rng_seed | 1;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    loop {
+        let magic = next() & next() & next();
+        // Good magics spread the mask's bits into the top byte
+        if (mask.0.wrapping_mul(magic) >> 56).count_ones() < 6 {
+            continue;
+        }
+        if magic_is_valid(sq, is_rook, magic, shift) {
+            return (magic, shift);
+        }
+    }
+}
+
+/// Check a whole table of magic numbers, using the standard shift for each square.
+/// Returns the first square whose magic collides.
+pub fn verify_magics(magics: &[u64; 64], is_rook: bool) -> Result<(), Square> {
+    for sq in Bitboard::UNIVERSE {
+        let (mask, _) = slider(sq, is_rook);
+        let shift = 64 - mask.count() as u8;
+        if !magic_is_valid(sq, is_rook, magics[sq.index() as usize], shift) {
+            return Err(sq);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{BISHOP_MAGICS, ROOK_MAGICS};
+
+    #[test]
+    fn test_shipped_magics_are_valid() {
+        assert_eq!(verify_magics(&ROOK_MAGICS.map(|m| m.magic), true), Ok(()));
+        assert_eq!(verify_magics(&BISHOP_MAGICS.map(|m| m.magic), false), Ok(()));
+
+        let mut broken = ROOK_MAGICS.map(|m| m.magic);
+        broken[27] = 1;
+        assert_eq!(verify_magics(&broken, true), Err(Square::D4));
+    }
+
+    #[test]
+    fn test_find_magic() {
+        for (sq, is_rook) in [(Square::A1, true), (Square::E4, true), (Square::C1, false)] {
+            let (magic, shift) = find_magic(sq, is_rook, 0x9E37_79B9_7F4A_7C15);
+            assert!(magic_is_valid(sq, is_rook, magic, shift));
+            assert_eq!(find_magic(sq, is_rook, 0x9E37_79B9_7F4A_7C15), (magic, shift));
+        }
+    }
+
+    #[test]
+    fn test_magic_is_valid_rejects_bad_shift() {
+        let magic = ROOK_MAGICS[0].magic;
+        for shift in [0, 1, 20, 51, 64, 255] {
+            assert!(!magic_is_valid(Square::A1, true, magic, shift), "shift {}", shift);
+        }
+        assert!(magic_is_valid(Square::A1, true, magic, 52));
+    }
+}
@@ -7,8 +7,9 @@ pub mod rays;
 pub mod magic;
 pub mod between;
 
-pub use pawn::{pawn_attacks, PAWN_ATTACKS};
+pub use pawn::{pawn_attacks, pawn_attacks_bb, PAWN_ATTACKS};
 pub use knight::{knight_attacks, KNIGHT_ATTACKS};
 pub use king::{king_attacks, KING_ATTACKS};
 pub use magic::{bishop_attacks, rook_attacks};
-pub use between::{between, line};
+pub use rays::{bishop_attacks_ray, rook_attacks_ray};
+pub use between::{aligned, between, between_inclusive, line, ray_between};
@@ -23,6 +23,40 @@ pub fn ray(dir: Direction, sq: Square) -> Bitboard {
     RAYS[idx][sq.index() as usize]
 }
 
+/// Get the attacks along one ray, stopping at (and including) the first blocker.
+///
+/// Rays pointing up the board find the nearest blocker with `lsb`, rays
+/// pointing down with `msb`.
+#[inline(always)]
+pub fn ray_attacks(dir: Direction, sq: Square, occ: Bitboard) -> Bitboard {
+    let attacks = ray(dir, sq);
+    let blocker = if dir.delta() > 0 { (attacks & occ).lsb() } else { (attacks & occ).msb() };
+    match blocker {
+        Some(blocker) => attacks ^ ray(dir, blocker),
+        None => attacks,
+    }
+}
+
+/// Get rook attacks by scanning each orthogonal ray for its first blocker.
+/// Slower than the magic lookup; useful as a table-light cross-check.
+#[inline]
+pub fn rook_attacks_ray(sq: Square, occ: Bitboard) -> Bitboard {
+    ray_attacks(Direction::North, sq, occ)
+        | ray_attacks(Direction::South, sq, occ)
+        | ray_attacks(Direction::East, sq, occ)
+        | ray_attacks(Direction::West, sq, occ)
+}
+
+/// Get bishop attacks by scanning each diagonal ray for its first blocker.
+/// Slower than the magic lookup; useful as a table-light cross-check.
+#[inline]
+pub fn bishop_attacks_ray(sq: Square, occ: Bitboard) -> Bitboard {
+    ray_attacks(Direction::NorthEast, sq, occ)
+        | ray_attacks(Direction::NorthWest, sq, occ)
+        | ray_attacks(Direction::SouthEast, sq, occ)
+        | ray_attacks(Direction::SouthWest, sq, occ)
+}
+
 /// Generate ray tables at compile time.
 const fn generate_rays() -> [[Bitboard; 64]; 8] {
     let mut rays = [[Bitboard::EMPTY; 64]; 8];
@@ -149,6 +183,20 @@ mod tests {
         assert!(!r.contains(Square::E3));
     }
 
+    #[test]
+    fn test_ray_attacks_match_magic() {
+        use crate::attacks::magic::{bishop_mask, enumerate_occupancies, rook_mask};
+        use crate::attacks::{bishop_attacks, rook_attacks};
+        for sq in Bitboard::UNIVERSE {
+            for occ in enumerate_occupancies(rook_mask(sq)) {
+                assert_eq!(rook_attacks_ray(sq, occ), rook_attacks(sq, occ));
+            }
+            for occ in enumerate_occupancies(bishop_mask(sq)) {
+                assert_eq!(bishop_attacks_ray(sq, occ), bishop_attacks(sq, occ));
+            }
+        }
+    }
+
     #[test]
     fn test_ray_diagonal() {
         let r = ray(Direction::NorthEast, Square::A1);
@@ -1,14 +1,18 @@
 //! CLI for move generation - outputs legal moves for a FEN position.
 
 use std::env;
-use movegen::Board;
+use movegen::{Board, MoveList, Square};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let san = args.iter().any(|a| a == "--san");
+    args.retain(|a| a != "--san");
     
     if args.len() < 2 {
-        eprintln!("Usage: movegen_cli <fen>");
+        eprintln!("Usage: movegen_cli [--san] <fen>");
         eprintln!("       movegen_cli perft <fen> <depth>");
+        eprintln!("       movegen_cli divide <fen> <depth>");
+        eprintln!("       movegen_cli [--san] from <fen> <square>");
         std::process::exit(1);
     }
     
@@ -37,19 +41,66 @@ fn main() {
                 std::process::exit(1);
             }
         }
+    } else if args[1] == "divide" {
+        if args.len() < 4 {
+            eprintln!("Usage: movegen_cli divide <fen> <depth>");
+            std::process::exit(1);
+        }
+        let fen = &args[2];
+        let depth: u32 = args[3].parse().unwrap_or(1);
+        
+        match Board::from_fen(fen) {
+            Ok(board) => {
+                // Same layout as Stockfish's `go perft`
+                let results = movegen::testing::perft_divide(&board, depth);
+                let total: u64 = results.iter().map(|(_, count)| count).sum();
+                for (mv, count) in results {
+                    println!("{}: {}", mv, count);
+                }
+                println!();
+                println!("Nodes searched: {}", total);
+            }
+            Err(e) => {
+                eprintln!("Invalid FEN: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else if args[1] == "from" {
+        if args.len() < 4 {
+            eprintln!("Usage: movegen_cli [--san] from <fen> <square>");
+            std::process::exit(1);
+        }
+        let board = match Board::from_fen(&args[2]) {
+            Ok(board) => board,
+            Err(e) => {
+                eprintln!("Invalid FEN: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let Some(sq) = Square::from_algebraic(&args[3]) else {
+            eprintln!("Invalid square: {}", args[3]);
+            std::process::exit(1);
+        };
+        match board.piece_at(sq) {
+            None => {
+                eprintln!("No piece on {}", sq);
+                std::process::exit(1);
+            }
+            Some((_, color)) if color != board.turn() => {
+                eprintln!("The piece on {} belongs to the side not to move", sq);
+                std::process::exit(1);
+            }
+            Some(_) => {}
+        }
+
+        print_moves(&board, &board.moves_from(sq), san);
     } else {
         // Assume the rest is a FEN string
         let fen = args[1..].join(" ");
         
         match Board::from_fen(&fen) {
             Ok(board) => {
-                let moves = board.generate_moves();
-                let mut uci_moves: Vec<String> = moves.iter().map(|m| m.to_uci()).collect();
-                uci_moves.sort();
-                
-                for mv in uci_moves {
-                    println!("{}", mv);
-                }
+                print_moves(&board, &board.generate_moves(), san);
             }
             Err(e) => {
                 eprintln!("Invalid FEN: {}", e);
@@ -58,3 +109,16 @@ fn main() {
         }
     }
 }
+
+/// Print moves one per line, sorted, in UCI or SAN.
+fn print_moves(board: &Board, moves: &MoveList, san: bool) {
+    let mut names: Vec<String> = moves
+        .iter()
+        .map(|m| if san { board.move_to_san(m) } else { board.move_to_uci(m) })
+        .collect();
+    names.sort();
+    
+    for name in names {
+        println!("{}", name);
+    }
+}
@@ -1,40 +1,40 @@
 //! Display utilities for debugging bitboards.
 
 use super::Bitboard;
-use core::fmt;
+use core::fmt::{self, Write};
 
 impl Bitboard {
     /// Pretty-print the bitboard as an 8x8 grid.
+    #[cfg(feature = "std")]
     pub fn pretty_print(&self) -> String {
-        let mut result = String::with_capacity(200);
-        result.push_str("  +---+---+---+---+---+---+---+---+\n");
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Bitboard {
+    /// Draw the 8x8 grid, rank 8 on top.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("  +---+---+---+---+---+---+---+---+\n")?;
         
         for rank in (0..8).rev() {
-            result.push_str(&format!("{} |", rank + 1));
+            write!(f, "{} |", rank + 1)?;
             for file in 0..8 {
                 let sq = rank * 8 + file;
                 let bit = (self.0 >> sq) & 1;
                 if bit == 1 {
-                    result.push_str(" X |");
+                    f.write_str(" X |")?;
                 } else {
-                    result.push_str("   |");
+                    f.write_str("   |")?;
                 }
             }
-            result.push('\n');
-            result.push_str("  +---+---+---+---+---+---+---+---+\n");
+            f.write_char('\n')?;
+            f.write_str("  +---+---+---+---+---+---+---+---+\n")?;
         }
-        result.push_str("    a   b   c   d   e   f   g   h\n");
-        result
-    }
-}
-
-impl fmt::Display for Bitboard {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.pretty_print())
+        f.write_str("    a   b   c   d   e   f   g   h\n")
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::types::Square;
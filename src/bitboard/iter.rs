@@ -39,6 +39,44 @@ impl Iterator for BitIterator {
 
 impl ExactSizeIterator for BitIterator {}
 
+/// Iterator that yields squares from the most significant bit down.
+#[derive(Clone, Copy)]
+pub struct ReverseBitIterator {
+    bits: Bitboard,
+}
+
+impl ReverseBitIterator {
+    /// Create a new reverse iterator from a bitboard.
+    #[inline(always)]
+    pub const fn new(bits: Bitboard) -> ReverseBitIterator {
+        ReverseBitIterator { bits }
+    }
+}
+
+impl Iterator for ReverseBitIterator {
+    type Item = Square;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Square> {
+        let sq = self.bits.msb()?;
+        self.bits ^= Bitboard::from_square(sq);
+        Some(sq)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.bits.count() as usize;
+        (count, Some(count))
+    }
+
+    #[inline(always)]
+    fn count(self) -> usize {
+        self.bits.count() as usize
+    }
+}
+
+impl ExactSizeIterator for ReverseBitIterator {}
+
 impl IntoIterator for Bitboard {
     type Item = Square;
     type IntoIter = BitIterator;
@@ -49,7 +87,25 @@ impl IntoIterator for Bitboard {
     }
 }
 
-#[cfg(test)]
+impl FromIterator<Square> for Bitboard {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Square>>(iter: I) -> Bitboard {
+        let mut bb = Bitboard::EMPTY;
+        bb.extend(iter);
+        bb
+    }
+}
+
+impl Extend<Square> for Bitboard {
+    #[inline]
+    fn extend<I: IntoIterator<Item = Square>>(&mut self, iter: I) {
+        for sq in iter {
+            *self |= Bitboard::from_square(sq);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -72,4 +128,26 @@ mod tests {
         let iter = bb.iter();
         assert_eq!(iter.len(), 2);
     }
+
+    #[test]
+    fn test_iter_rev() {
+        let bb = Bitboard::from_square(Square::A1)
+               | Bitboard::from_square(Square::E4)
+               | Bitboard::from_square(Square::H8);
+        let squares: Vec<_> = bb.iter_rev().collect();
+        assert_eq!(squares, [Square::H8, Square::E4, Square::A1]);
+        assert_eq!(bb.iter_rev().len(), 3);
+        assert_eq!(Bitboard::EMPTY.iter_rev().next(), None);
+    }
+
+    #[test]
+    fn test_collect() {
+        let bb: Bitboard = [Square::A1, Square::E4, Square::A1].into_iter().collect();
+        assert_eq!(bb, Bitboard::from_square(Square::A1) | Bitboard::from_square(Square::E4));
+
+        let mut bb = bb;
+        bb.extend([Square::H8]);
+        assert_eq!(bb.iter().collect::<Bitboard>(), bb);
+        assert_eq!(bb.count(), 3);
+    }
 }
@@ -7,7 +7,7 @@ mod iter;
 mod ops;
 mod display;
 
-pub use iter::BitIterator;
+pub use iter::{BitIterator, ReverseBitIterator};
 
 use core::fmt;
 use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, Shr};
@@ -15,6 +15,7 @@ use crate::types::Square;
 
 /// A 64-bit bitboard representing squares on the chess board.
 #[derive(Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct Bitboard(pub u64);
 
@@ -51,6 +52,10 @@ impl Bitboard {
     pub const NOT_FILE_AB: Bitboard = Bitboard(!0x0303030303030303);
     pub const NOT_FILE_GH: Bitboard = Bitboard(!0xC0C0C0C0C0C0C0C0);
 
+    // Square colors (a1 is dark)
+    pub const LIGHT_SQUARES: Bitboard = Bitboard(0x55AA55AA55AA55AA);
+    pub const DARK_SQUARES: Bitboard = Bitboard(0xAA55AA55AA55AA55);
+
     // Useful for castling
     pub const BETWEEN_E1_G1: Bitboard = Bitboard(0x60);  // F1 | G1
     pub const BETWEEN_E1_C1: Bitboard = Bitboard(0x0E);  // B1 | C1 | D1
@@ -187,6 +192,12 @@ impl Bitboard {
         BitIterator::new(self)
     }
 
+    /// Iterate over set squares from h8 down to a1.
+    #[inline]
+    pub fn iter_rev(self) -> ReverseBitIterator {
+        ReverseBitIterator::new(self)
+    }
+
     /// Shift north (toward rank 8).
     #[inline(always)]
     pub const fn north(self) -> Bitboard {
@@ -241,6 +252,28 @@ impl Bitboard {
         Bitboard(self.0.swap_bytes())
     }
 
+    /// Flip across the a1-h8 diagonal (swap files and ranks).
+    #[inline(always)]
+    pub const fn flip_diagonal(self) -> Bitboard {
+        const K1: u64 = 0x5500_5500_5500_5500;
+        const K2: u64 = 0x3333_0000_3333_0000;
+        const K4: u64 = 0x0F0F_0F0F_0000_0000;
+        let mut x = self.0;
+        let mut t = K4 & (x ^ (x << 28));
+        x ^= t ^ (t >> 28);
+        t = K2 & (x ^ (x << 14));
+        x ^= t ^ (t >> 14);
+        t = K1 & (x ^ (x << 7));
+        x ^= t ^ (t >> 7);
+        Bitboard(x)
+    }
+
+    /// Rotate 90 degrees clockwise (a1 goes to a8, a8 to h8).
+    #[inline(always)]
+    pub const fn rotate_90_cw(self) -> Bitboard {
+        self.flip_diagonal().flip_vertical()
+    }
+
     /// Get file mask for a given file index.
     #[inline(always)]
     pub const fn file_mask(file: crate::types::File) -> Bitboard {
@@ -356,6 +389,21 @@ mod tests {
     use super::*;
     use crate::types::Square;
 
+    #[test]
+    fn test_flip_diagonal_and_rotate() {
+        assert_eq!(Bitboard::FILE_A.flip_diagonal(), Bitboard::RANK_1);
+        assert_eq!(Bitboard::RANK_8.flip_diagonal(), Bitboard::file_mask(crate::types::File::H));
+        assert_eq!(Bitboard::from_square(Square::B1).flip_diagonal(), Bitboard::from_square(Square::A2));
+        let diagonal = crate::attacks::line(Square::A1, Square::H8);
+        assert_eq!(diagonal.flip_diagonal(), diagonal);
+
+        assert_eq!(Bitboard::FILE_A.rotate_90_cw(), Bitboard::RANK_8);
+        assert_eq!(Bitboard::RANK_1.rotate_90_cw(), Bitboard::FILE_A);
+        assert_eq!(Bitboard::from_square(Square::H1).rotate_90_cw(), Bitboard::from_square(Square::A1));
+        let bb = Bitboard::new(0x0123_4567_89AB_CDEF);
+        assert_eq!(bb.rotate_90_cw().rotate_90_cw().rotate_90_cw().rotate_90_cw(), bb);
+    }
+
     #[test]
     fn test_empty_universe() {
         assert!(Bitboard::EMPTY.is_empty());
@@ -402,6 +450,15 @@ mod tests {
         assert!(bb.west().contains(Square::D4));
     }
 
+    #[test]
+    fn test_square_colors() {
+        assert!(Bitboard::DARK_SQUARES.contains(Square::A1));
+        assert!(Bitboard::LIGHT_SQUARES.contains(Square::H1));
+        assert!(Bitboard::LIGHT_SQUARES.contains(Square::D1));
+        assert!(Bitboard::DARK_SQUARES.contains(Square::H8));
+        assert_eq!(Bitboard::LIGHT_SQUARES | Bitboard::DARK_SQUARES, Bitboard::UNIVERSE);
+    }
+
     #[test]
     fn test_edge_shifts() {
         // A file piece can't go west
@@ -0,0 +1,30 @@
+//! Hooks for incrementally updated evaluation state.
+
+use crate::types::{Square, Piece, Color};
+
+/// Receives piece placement changes from [`Board::make_move_with`] and
+/// [`Board::unmake_move_with`].
+///
+/// All methods default to no-ops, so implementors only override what they need.
+///
+/// [`Board::make_move_with`]: super::Board::make_move_with
+/// [`Board::unmake_move_with`]: super::Board::unmake_move_with
+pub trait Accumulator {
+    /// A piece was placed on `sq`.
+    #[inline(always)]
+    fn on_add(&mut self, _piece: Piece, _color: Color, _sq: Square) {}
+
+    /// A piece was removed from `sq`.
+    #[inline(always)]
+    fn on_remove(&mut self, _piece: Piece, _color: Color, _sq: Square) {}
+
+    /// A piece moved from `from` to `to`.
+    #[inline(always)]
+    fn on_move(&mut self, _piece: Piece, _color: Color, _from: Square, _to: Square) {}
+}
+
+/// Accumulator that ignores every update.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoAccumulator;
+
+impl Accumulator for NoAccumulator {}
@@ -0,0 +1,162 @@
+//! Human-readable board rendering.
+
+use core::fmt::{self, Write};
+use super::Board;
+#[cfg(feature = "std")]
+use crate::movegen::Move;
+use crate::types::{Square, File, Rank};
+#[cfg(feature = "std")]
+use crate::types::{Piece, Color};
+
+/// Unicode glyph for a piece.
+#[cfg(feature = "std")]
+const fn unicode_glyph(piece: Piece, color: Color) -> char {
+    match (color, piece) {
+        (Color::White, Piece::Pawn) => '♙',
+        (Color::White, Piece::Knight) => '♘',
+        (Color::White, Piece::Bishop) => '♗',
+        (Color::White, Piece::Rook) => '♖',
+        (Color::White, Piece::Queen) => '♕',
+        (Color::White, Piece::King) => '♔',
+        (Color::Black, Piece::Pawn) => '♟',
+        (Color::Black, Piece::Knight) => '♞',
+        (Color::Black, Piece::Bishop) => '♝',
+        (Color::Black, Piece::Rook) => '♜',
+        (Color::Black, Piece::Queen) => '♛',
+        (Color::Black, Piece::King) => '♚',
+    }
+}
+
+impl Board {
+    /// Render the board as an ASCII grid with piece letters (uppercase
+    /// white, lowercase black), coordinates, and the side to move.
+    #[cfg(feature = "std")]
+    pub fn pretty_print(&self) -> String {
+        self.to_string()
+    }
+
+    /// Render the board with Unicode glyphs, white at the bottom.
+    #[cfg(feature = "std")]
+    pub fn to_unicode(&self) -> String {
+        self.to_unicode_with_coords(false)
+    }
+
+    /// Render the board with Unicode glyphs, rank/file coordinates, and
+    /// the side to move. With `flip`, black is shown at the bottom.
+    #[cfg(feature = "std")]
+    pub fn to_unicode_with_coords(&self, flip: bool) -> String {
+        self.to_unicode_with_last_move(flip, None)
+    }
+
+    /// Like [`Board::to_unicode_with_coords`], marking the from and to
+    /// squares of `last_move` with `*`.
+    #[cfg(feature = "std")]
+    pub fn to_unicode_with_last_move(&self, flip: bool, last_move: Option<Move>) -> String {
+        let mut result = String::with_capacity(256);
+        let ranks: [Rank; 8] = if flip { Rank::ALL } else { rank_desc() };
+        let files: [File; 8] = if flip { file_desc() } else { File::ALL };
+        let marked = |sq: Square| last_move.is_some_and(|m| m.from() == sq || m.to() == sq);
+
+        for rank in ranks {
+            result.push(rank.to_char());
+            result.push(' ');
+            for file in files {
+                let sq = Square::from_file_rank(file, rank);
+                result.push(match self.piece_at(sq) {
+                    Some((piece, color)) => unicode_glyph(piece, color),
+                    None => '·',
+                });
+                result.push(if marked(sq) { '*' } else { ' ' });
+            }
+            result.push('\n');
+        }
+
+        result.push_str("  ");
+        for file in files {
+            result.push(file.to_char());
+            result.push(' ');
+        }
+        result.push('\n');
+        result.push_str(if self.turn.is_white() { "White to move\n" } else { "Black to move\n" });
+        result
+    }
+}
+
+impl fmt::Display for Board {
+    /// Draw the ASCII grid with piece letters (uppercase white,
+    /// lowercase black), coordinates, and the side to move.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("  +---+---+---+---+---+---+---+---+\n")?;
+
+        for rank in rank_desc() {
+            f.write_char(rank.to_char())?;
+            f.write_str(" |")?;
+            for file in File::ALL {
+                let sq = Square::from_file_rank(file, rank);
+                f.write_char(' ')?;
+                f.write_char(match self.piece_at(sq) {
+                    Some((piece, color)) => piece.to_char(color),
+                    None => ' ',
+                })?;
+                f.write_str(" |")?;
+            }
+            f.write_char('\n')?;
+            f.write_str("  +---+---+---+---+---+---+---+---+\n")?;
+        }
+        f.write_str("    a   b   c   d   e   f   g   h\n")?;
+        f.write_str(if self.turn.is_white() { "White to move\n" } else { "Black to move\n" })
+    }
+}
+
+/// Ranks from 8 down to 1.
+const fn rank_desc() -> [Rank; 8] {
+    [Rank::R8, Rank::R7, Rank::R6, Rank::R5, Rank::R4, Rank::R3, Rank::R2, Rank::R1]
+}
+
+/// Files from H down to A.
+#[cfg(feature = "std")]
+const fn file_desc() -> [File; 8] {
+    [File::H, File::G, File::F, File::E, File::D, File::C, File::B, File::A]
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_print() {
+        let output = Board::startpos().pretty_print();
+        assert!(output.starts_with("  +---+"));
+        assert!(output.contains("8 | r | n | b | q | k | b | n | r |"));
+        assert!(output.contains("4 |   |   |   |   |   |   |   |   |"));
+        assert!(output.contains("1 | R | N | B | Q | K | B | N | R |"));
+        assert!(output.ends_with("White to move\n"));
+        assert_eq!(Board::startpos().to_string(), output);
+    }
+
+    #[test]
+    fn test_unicode_startpos() {
+        let board = Board::startpos();
+        let output = board.to_unicode();
+        assert_eq!(output, board.to_unicode_with_coords(false));
+        assert!(output.contains('♚'));
+        assert!(output.starts_with("8 ♜"));
+        assert!(output.contains("White to move"));
+
+        // Flipped: rank 1 on top, files run h to a
+        let flipped = board.to_unicode_with_coords(true);
+        assert!(flipped.starts_with("1 ♖"));
+        assert!(flipped.contains("  h g f e d c b a"));
+    }
+
+    #[test]
+    fn test_unicode_last_move() {
+        use crate::movegen::MoveFlag;
+        let mv = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
+        let board = Board::startpos().make_move_new(mv);
+        let output = board.to_unicode_with_last_move(false, Some(mv));
+        assert!(output.contains("♙*"));
+        assert!(output.contains("·*"));
+        assert!(output.contains("Black to move"));
+    }
+}
@@ -1,37 +1,177 @@
 //! FEN parsing and serialization.
 
+use core::fmt;
 use super::Board;
+use crate::bitboard::Bitboard;
 use crate::types::{Square, File, Rank, Piece, Color, CastleRights};
 
+/// Error returned when a FEN string cannot be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FenError {
+    /// Fewer than the four required fields.
+    TooFewFields,
+    /// More than eight ranks in the piece placement.
+    TooManyRanks,
+    /// Fewer than eight ranks in the piece placement (strict mode).
+    TooFewRanks,
+    /// More than eight squares in one rank of the piece placement;
+    /// `rank` is 1-based.
+    TooManyPieces { rank: u8 },
+    /// Fewer than eight squares in one rank of the piece placement
+    /// (strict mode); `rank` is 1-based.
+    TooFewSquares { rank: u8 },
+    /// A character in the piece placement that is not a piece, digit or `/`;
+    /// `rank` is 1-based.
+    InvalidPieceChar { rank: u8, ch: char },
+    /// Side to move is not `w` or `b`.
+    BadSideToMove,
+    /// Castling field is not `-`, `KQkq`-style or rook files.
+    BadCastling,
+    /// Castling rook files given without a king on the back rank.
+    CastlingKingNotOnBackRank,
+    /// En passant field is not `-` or a square.
+    BadEnPassant,
+    /// Halfmove clock is not a number.
+    BadHalfmove,
+    /// Fullmove number is not a number.
+    BadFullmove,
+    /// A side does not have exactly one king (strict mode).
+    BadKingCount,
+    /// A pawn stands on the first or last rank (strict mode).
+    PawnOnBackRank,
+    /// The side not to move is in check (strict mode).
+    OpponentInCheck,
+    /// Two pieces were placed on the same square.
+    DuplicateSquare(Square),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::TooFewFields => write!(f, "FEN must have at least 4 parts"),
+            FenError::TooManyRanks => write!(f, "Too many ranks in FEN"),
+            FenError::TooFewRanks => write!(f, "Too few ranks in FEN"),
+            FenError::TooManyPieces { .. } => write!(f, "Too many pieces in rank"),
+            FenError::TooFewSquares { .. } => write!(f, "Too few squares in rank"),
+            FenError::InvalidPieceChar { .. } => write!(f, "Invalid piece character"),
+            FenError::BadSideToMove => write!(f, "Invalid side to move"),
+            FenError::BadCastling => write!(f, "Invalid castling rights"),
+            FenError::CastlingKingNotOnBackRank => write!(f, "Castling king not on back rank"),
+            FenError::BadEnPassant => write!(f, "Invalid en passant square"),
+            FenError::BadHalfmove => write!(f, "Invalid halfmove clock"),
+            FenError::BadFullmove => write!(f, "Invalid fullmove number"),
+            FenError::BadKingCount => write!(f, "Each side must have exactly one king"),
+            FenError::PawnOnBackRank => write!(f, "Pawn on first or last rank"),
+            FenError::OpponentInCheck => write!(f, "Side not to move is in check"),
+            FenError::DuplicateSquare(sq) => write!(f, "Square {} used twice", sq),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FenError {}
+
+impl core::str::FromStr for Board {
+    type Err = FenError;
+
+    /// Parse a FEN string; same as [`Board::from_fen`].
+    fn from_str(fen: &str) -> Result<Board, FenError> {
+        Board::from_fen(fen)
+    }
+}
+
 impl Board {
     /// Parse a FEN string into a board.
-    pub fn from_fen(fen: &str) -> Result<Board, &'static str> {
-        let mut board = Board::empty();
-        let parts: Vec<&str> = fen.split_whitespace().collect();
-        
-        if parts.len() < 4 {
-            return Err("FEN must have at least 4 parts");
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        let mut board = Board::parse_fen(fen, false)?;
+        board.update_checkers();
+        Ok(board)
+    }
+
+    /// Parse a FEN string, rejecting positions that cannot occur in a game.
+    ///
+    /// On top of [`Board::from_fen`], requires exactly eight ranks of eight
+    /// squares in the piece placement, exactly one king per side, no
+    /// pawns on the first or last rank, the side not to move out of check,
+    /// an en passant square just behind a pawn that could have double-pushed,
+    /// and castling rights backed by king and rook on their home squares.
+    pub fn from_fen_strict(fen: &str) -> Result<Board, FenError> {
+        let mut board = Board::parse_fen(fen, true)?;
+        board.validate()?;
+        board.update_checkers();
+        Ok(board)
+    }
+
+    /// Check the position rules enforced by [`Board::from_fen_strict`].
+    pub(crate) fn validate(&self) -> Result<(), FenError> {
+        for color in Color::ALL {
+            if !self.piece_color_bb(Piece::King, color).exactly_one() {
+                return Err(FenError::BadKingCount);
+            }
+        }
+        if (self.piece_bb(Piece::Pawn) & (Bitboard::RANK_1 | Bitboard::RANK_8)).any() {
+            return Err(FenError::PawnOnBackRank);
+        }
+        if self.is_attacked(self.king_square(!self.turn), self.turn) {
+            return Err(FenError::OpponentInCheck);
+        }
+
+        if let Some(ep) = self.ep_square {
+            // The pushed pawn is one step past `ep`, and it came from one step before
+            let them = !self.turn;
+            let push = them.pawn_push();
+            let pawn = Square::from_index((ep.index() as i8 + push) as u8);
+            let origin = Square::from_index((ep.index() as i8 - push) as u8);
+            let ok = origin.is_some_and(|sq| sq.rank() == them.pawn_rank() && !self.occupied().contains(sq))
+                && pawn.is_some_and(|sq| self.piece_color_bb(Piece::Pawn, them).contains(sq))
+                && !self.occupied().contains(ep);
+            if !ok {
+                return Err(FenError::BadEnPassant);
+            }
+        }
+
+        if self.supported_castling() != self.castling {
+            return Err(FenError::BadCastling);
         }
+        Ok(())
+    }
+
+    /// Parse the FEN fields without computing checkers. `strict` rejects a
+    /// piece placement that is not exactly eight ranks of eight squares.
+    fn parse_fen(fen: &str, strict: bool) -> Result<Board, FenError> {
+        let mut board = Board::empty();
+        let mut parts = fen.split_whitespace();
+        let (Some(placement), Some(side), Some(castling), Some(ep)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(FenError::TooFewFields);
+        };
         
         // Parse piece placement
         let mut rank = 7u8;
         let mut file = 0u8;
         
-        for c in parts[0].chars() {
+        for c in placement.chars() {
             match c {
                 '/' => {
                     if rank == 0 {
-                        return Err("Too many ranks in FEN");
+                        return Err(FenError::TooManyRanks);
+                    }
+                    if strict && file < 8 {
+                        return Err(FenError::TooFewSquares { rank: rank + 1 });
                     }
                     rank -= 1;
                     file = 0;
                 }
                 '1'..='8' => {
-                    file += (c as u8) - b'0';
+                    file = file.saturating_add((c as u8) - b'0');
+                    if strict && file > 8 {
+                        return Err(FenError::TooManyPieces { rank: rank + 1 });
+                    }
                 }
                 _ => {
                     if file >= 8 {
-                        return Err("Too many pieces in rank");
+                        return Err(FenError::TooManyPieces { rank: rank + 1 });
                     }
                     if let Some((piece, color)) = Piece::from_char(c) {
                         let sq = Square::from_file_rank(
@@ -41,57 +181,211 @@ impl Board {
                         board.add_piece(sq, piece, color);
                         file += 1;
                     } else {
-                        return Err("Invalid piece character");
+                        return Err(FenError::InvalidPieceChar { rank: rank + 1, ch: c });
                     }
                 }
             }
         }
+        if strict && rank > 0 {
+            return Err(FenError::TooFewRanks);
+        }
+        if strict && file < 8 {
+            return Err(FenError::TooFewSquares { rank: 1 });
+        }
         
         // Parse side to move
-        board.turn = match parts[1] {
+        board.turn = match side {
             "w" => Color::White,
             "b" => Color::Black,
-            _ => return Err("Invalid side to move"),
+            _ => return Err(FenError::BadSideToMove),
         };
         if board.turn == Color::Black {
             board.hash ^= super::zobrist::ZOBRIST.side();
         }
         
         // Parse castling rights
-        board.castling = CastleRights::from_fen(parts[2])
-            .ok_or("Invalid castling rights")?;
+        board.castling = board.parse_castling(castling)?;
         board.hash ^= super::zobrist::ZOBRIST.castling(board.castling);
         
         // Parse en passant
-        board.ep_square = if parts[3] == "-" {
+        board.ep_square = if ep == "-" {
             None
         } else {
-            let sq = Square::from_algebraic(parts[3])
-                .ok_or("Invalid en passant square")?;
-            board.hash ^= super::zobrist::ZOBRIST.ep_file(sq.file());
+            let sq = Square::from_algebraic(ep)
+                .ok_or(FenError::BadEnPassant)?;
+            if board.ep_capturable_by(sq, board.turn) {
+                board.hash ^= super::zobrist::ZOBRIST.ep_file(sq.file());
+            }
             Some(sq)
         };
         
         // Parse halfmove clock (optional)
-        if parts.len() > 4 {
-            board.halfmove_clock = parts[4].parse().unwrap_or(0);
+        if let Some(halfmove) = parts.next() {
+            board.halfmove_clock = halfmove.parse().map_err(|_| FenError::BadHalfmove)?;
         }
         
         // Parse fullmove number (optional)
-        if parts.len() > 5 {
-            board.fullmove_number = parts[5].parse().unwrap_or(1);
+        if let Some(fullmove) = parts.next() {
+            board.fullmove_number = fullmove.parse().map_err(|_| FenError::BadFullmove)?;
         }
         
-        // Compute checkers
-        board.update_checkers();
-        
         Ok(board)
     }
 
+    /// Parse the castling field, recording the castling rook squares.
+    ///
+    /// Accepts `KQkq` as well as Shredder-FEN rook files (`HAha`). In X-FEN
+    /// style, `K`/`Q` with the king off the e-file name the outermost rook
+    /// on that side. Anything other than corner rooks with the king on the
+    /// e-file switches the board to Chess960 castling.
+    fn parse_castling(&mut self, field: &str) -> Result<CastleRights, FenError> {
+        if field == "-" {
+            return Ok(CastleRights::NONE);
+        }
+
+        let mut rights = CastleRights::NONE;
+        for c in field.chars() {
+            let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+            let rank = color.back_rank();
+            let kings = self.piece_color_bb(Piece::King, color) & Bitboard::rank_mask(rank);
+            let king_file = kings.lsb().map(|sq| sq.file());
+            let rooks = self.piece_color_bb(Piece::Rook, color) & Bitboard::rank_mask(rank);
+
+            let (kingside, rook_file) = match c.to_ascii_lowercase() {
+                'k' | 'q' => {
+                    let kingside = c.eq_ignore_ascii_case(&'k');
+                    let standard = if kingside { File::H } else { File::A };
+                    // X-FEN: outermost rook beyond a king that is not on the e-file
+                    let outer = king_file.filter(|&f| f != File::E).and_then(|king| {
+                        let mut side = rooks.into_iter().filter(|sq| (sq.file() > king) == kingside);
+                        if kingside { side.last() } else { side.next() }
+                    });
+                    (kingside, outer.map_or(standard, |sq| sq.file()))
+                }
+                'a'..='h' => {
+                    let file = File::from_char(c).ok_or(FenError::BadCastling)?;
+                    let king = king_file.ok_or(FenError::CastlingKingNotOnBackRank)?;
+                    self.chess960 = true;
+                    (file > king, file)
+                }
+                _ => return Err(FenError::BadCastling),
+            };
+
+            let rook_sq = Square::from_file_rank(rook_file, rank);
+            let right = match (color, kingside) {
+                (Color::White, true) => CastleRights::WHITE_KINGSIDE,
+                (Color::White, false) => CastleRights::WHITE_QUEENSIDE,
+                (Color::Black, true) => CastleRights::BLACK_KINGSIDE,
+                (Color::Black, false) => CastleRights::BLACK_QUEENSIDE,
+            };
+            let standard = (if kingside { File::H } else { File::A }) == rook_file;
+            if !standard || king_file.is_some_and(|f| f != File::E) {
+                self.chess960 = true;
+            }
+            self.castle_rooks[right.bits().trailing_zeros() as usize] = rook_sq;
+            rights = rights.add(right);
+        }
+        Ok(rights)
+    }
+
+    /// Parse a FEN string, dropping castling rights the position cannot support.
+    ///
+    /// Many FENs in the wild carry stale rights such as `KQkq` after a rook
+    /// has already moved. A right is kept only if the king stands on its home
+    /// square (E1/E8) and a rook of the same color stands on the matching
+    /// corner (H1/A1/H8/A8); every other right is cleared and the hash is
+    /// updated accordingly. [`Board::from_fen`] keeps the rights verbatim.
+    pub fn from_fen_normalized(fen: &str) -> Result<Board, FenError> {
+        let mut board = Board::from_fen(fen)?;
+        board.normalize_castling();
+        Ok(board)
+    }
+
+    /// Clear castling rights not backed by a king and rook on their home squares.
+    fn normalize_castling(&mut self) {
+        self.hash ^= super::zobrist::ZOBRIST.castling(self.castling);
+        self.castling = self.supported_castling();
+        self.hash ^= super::zobrist::ZOBRIST.castling(self.castling);
+    }
+
+    /// The subset of the castling rights backed by king and rook on their
+    /// home squares. In Chess960 the king only needs to be on the back
+    /// rank, on the near side of its castling rook.
+    fn supported_castling(&self) -> CastleRights {
+        const RIGHTS: [(CastleRights, Color, bool); 4] = [
+            (CastleRights::WHITE_KINGSIDE, Color::White, true),
+            (CastleRights::WHITE_QUEENSIDE, Color::White, false),
+            (CastleRights::BLACK_KINGSIDE, Color::Black, true),
+            (CastleRights::BLACK_QUEENSIDE, Color::Black, false),
+        ];
+
+        let mut rights = self.castling;
+        for (right, color, kingside) in RIGHTS {
+            let rook_sq = self.castle_rook_square(color, kingside);
+            let kings = self.piece_color_bb(Piece::King, color);
+            let king_home = if self.chess960 {
+                (kings & Bitboard::rank_mask(color.back_rank()))
+                    .lsb()
+                    .is_some_and(|king| (rook_sq.file() > king.file()) == kingside)
+            } else {
+                kings.contains(Square::from_file_rank(File::E, color.back_rank()))
+            };
+            let rook_home = self.piece_color_bb(Piece::Rook, color).contains(rook_sq);
+            if !king_home || !rook_home {
+                rights = rights.remove(right);
+            }
+        }
+        rights
+    }
+
+    /// Check that `to_fen` followed by `from_fen` reproduces this position.
+    /// Move counters are ignored. Intended as an invariant check in tests.
+    #[cfg(feature = "std")]
+    pub fn fen_roundtrip_ok(&self) -> bool {
+        match Board::from_fen(&self.to_fen()) {
+            Ok(other) => *self == other && self.hash == other.hash,
+            Err(_) => false,
+        }
+    }
+
+    /// Write castling rights as Shredder-FEN rook files, e.g. `HFhf`.
+    fn write_shredder_castling<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        for (i, &rook) in self.castle_rooks.iter().enumerate() {
+            if self.castling.bits() & (1 << i) != 0 {
+                let c = rook.file().to_char();
+                w.write_char(if i < 2 { c.to_ascii_uppercase() } else { c })?;
+            }
+        }
+        Ok(())
+    }
+
     /// Convert board to FEN string.
+    ///
+    /// Chess960 positions write castling rights as rook files (`HAha`).
+    #[cfg(feature = "std")]
     pub fn to_fen(&self) -> String {
         let mut fen = String::with_capacity(80);
-        
+        self.write_fen(&mut fen).expect("writing to a String cannot fail");
+        fen
+    }
+
+    /// Convert board to an EPD position string: the first four FEN fields
+    /// (placement, side to move, castling, en passant) without move counters.
+    #[cfg(feature = "std")]
+    pub fn to_epd(&self) -> String {
+        let mut epd = String::with_capacity(80);
+        self.write_position(&mut epd).expect("writing to a String cannot fail");
+        epd
+    }
+
+    /// Write the FEN into any [`fmt::Write`] without allocating.
+    pub fn write_fen<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        self.write_position(w)?;
+        write!(w, " {} {}", self.halfmove_clock, self.fullmove_number)
+    }
+
+    /// Write the four position fields shared by FEN and EPD.
+    fn write_position<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
         // Piece placement
         for rank in (0..8).rev() {
             let mut empty_count = 0;
@@ -104,55 +398,48 @@ impl Board {
                 
                 if let Some((piece, color)) = self.piece_at(sq) {
                     if empty_count > 0 {
-                        fen.push((b'0' + empty_count) as char);
+                        w.write_char((b'0' + empty_count) as char)?;
                         empty_count = 0;
                     }
-                    fen.push(piece.to_char(color));
+                    w.write_char(piece.to_char(color))?;
                 } else {
                     empty_count += 1;
                 }
             }
             
             if empty_count > 0 {
-                fen.push((b'0' + empty_count) as char);
+                w.write_char((b'0' + empty_count) as char)?;
             }
             
             if rank > 0 {
-                fen.push('/');
+                w.write_char('/')?;
             }
         }
         
         // Side to move
-        fen.push(' ');
-        fen.push(if self.turn == Color::White { 'w' } else { 'b' });
+        w.write_str(if self.turn == Color::White { " w " } else { " b " })?;
         
         // Castling rights
-        fen.push(' ');
-        fen.push_str(self.castling.to_fen());
+        if self.chess960 && self.castling.any() {
+            self.write_shredder_castling(w)?;
+        } else {
+            w.write_str(self.castling.to_fen())?;
+        }
         
         // En passant
-        fen.push(' ');
+        w.write_char(' ')?;
         if let Some(sq) = self.ep_square {
             let [f, r] = sq.to_algebraic();
-            fen.push(f);
-            fen.push(r);
+            w.write_char(f)?;
+            w.write_char(r)?;
         } else {
-            fen.push('-');
+            w.write_char('-')?;
         }
-        
-        // Halfmove clock
-        fen.push(' ');
-        fen.push_str(&self.halfmove_clock.to_string());
-        
-        // Fullmove number
-        fen.push(' ');
-        fen.push_str(&self.fullmove_number.to_string());
-        
-        fen
+        Ok(())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -165,6 +452,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_write_fen_no_alloc() {
+        struct Buf([u8; 96], usize);
+        impl fmt::Write for Buf {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let end = self.1 + s.len();
+                self.0.get_mut(self.1..end).ok_or(fmt::Error)?.copy_from_slice(s.as_bytes());
+                self.1 = end;
+                Ok(())
+            }
+        }
+
+        let board = Board::from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 3 17").unwrap();
+        let mut buf = Buf([0; 96], 0);
+        board.write_fen(&mut buf).unwrap();
+        assert_eq!(&buf.0[..buf.1], board.to_fen().as_bytes());
+        assert_eq!(board.to_fen(), "r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 3 17");
+    }
+
+    #[test]
+    fn test_to_epd() {
+        assert_eq!(Board::startpos().to_epd(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -");
+
+        let board = Board::from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 3 17").unwrap();
+        assert_eq!(board.to_epd(), "r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6");
+        assert_eq!(Board::from_fen(&board.to_epd()).unwrap(), board);
+    }
+
     #[test]
     fn test_fen_roundtrip() {
         let fens = [
@@ -176,9 +491,143 @@ mod tests {
         
         for fen in fens {
             let board = Board::from_fen(fen).unwrap();
-            // Note: FEN might differ slightly due to normalization
-            let _ = board.to_fen();
+            assert_eq!(board.to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn test_fen_roundtrip_ok() {
+        use crate::testing::{STARTPOS, KIWIPETE, POSITION_3, POSITION_4, POSITION_5, POSITION_6};
+        use rand::{Rng, SeedableRng};
+
+        let mut boards = Vec::new();
+        for fen in [STARTPOS, KIWIPETE, POSITION_3, POSITION_4, POSITION_5, POSITION_6] {
+            boards.push(Board::from_fen(fen).unwrap());
         }
+
+        // Random playouts from the start position
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x5EED);
+        for _ in 0..50 {
+            let mut board = Board::startpos();
+            for _ in 0..rng.gen_range(1..60) {
+                let moves = board.generate_moves();
+                if moves.is_empty() {
+                    break;
+                }
+                board = board.make_move_new(moves.get(rng.gen_range(0..moves.len())).unwrap());
+            }
+            boards.push(board);
+        }
+
+        for board in &boards {
+            assert!(board.fen_roundtrip_ok(), "roundtrip failed: {}", board.to_fen());
+        }
+    }
+
+    #[test]
+    fn test_from_fen_normalized_castling() {
+        // Claims white kingside rights, but there is no rook on h1
+        let fen = "r3k2r/8/8/8/8/8/8/R3K3 w KQkq - 0 1";
+        let board = Board::from_fen_normalized(fen).unwrap();
+        assert!(!board.castling().has_white_kingside());
+        assert!(board.castling().has_white_queenside());
+        assert!(board.castling().has_black_kingside());
+        assert!(board.castling().has_black_queenside());
+
+        // Hash must match a board parsed with the corrected rights
+        let expected = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K3 w Qkq - 0 1").unwrap();
+        assert_eq!(board.hash(), expected.hash());
+
+        // Plain parsing keeps the declared rights
+        assert!(Board::from_fen(fen).unwrap().castling().has_white_kingside());
+    }
+
+    #[test]
+    fn test_chess960_castling_fields() {
+        let shredder = Board::from_fen("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9").unwrap();
+        let xfen = Board::from_fen("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9").unwrap();
+        for board in [shredder, xfen] {
+            assert!(board.is_chess960());
+            assert_eq!(board.castling(), CastleRights::ALL);
+            assert_eq!(board.castle_rook_square(Color::White, true), Square::H1);
+            assert_eq!(board.castle_rook_square(Color::White, false), Square::F1);
+            assert_eq!(board.castle_rook_square(Color::Black, false), Square::F8);
+        }
+        assert_eq!(shredder.hash(), xfen.hash());
+        assert!(!Board::startpos().is_chess960());
+    }
+
+    #[test]
+    fn test_chess960_fen_roundtrip() {
+        for fen in [
+            "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w GEge - 0 1",
+            "nrbbqknr/pppppppp/8/8/8/8/PPPPPPPP/NRBBQKNR w HBhb - 0 1",
+            "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w CAca - 0 1",
+            "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            assert_eq!(board.to_fen(), fen);
+            assert!(board.fen_roundtrip_ok());
+        }
+
+        // X-FEN input comes back in Shredder form
+        let board = Board::from_fen("nrbbqknr/pppppppp/8/8/8/8/PPPPPPPP/NRBBQKNR w KQkq - 0 1").unwrap();
+        assert_eq!(board.to_fen(), "nrbbqknr/pppppppp/8/8/8/8/PPPPPPPP/NRBBQKNR w HBhb - 0 1");
+    }
+
+    #[test]
+    fn test_fen_errors() {
+        assert_eq!(Board::from_fen("8/8/8 w -").unwrap_err(), FenError::TooFewFields);
+        assert_eq!(
+            Board::from_fen("rnbqkbnr/ppppxppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap_err(),
+            FenError::InvalidPieceChar { rank: 7, ch: 'x' }
+        );
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/8/8 w - - 0 1").unwrap_err(), FenError::TooManyRanks);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3p w - - 0 1").unwrap_err(), FenError::TooManyPieces { rank: 1 });
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/4K3/7R w H - 0 1").unwrap_err(),
+            FenError::CastlingKingNotOnBackRank
+        );
+        assert_eq!(FenError::TooManyRanks.to_string(), "Too many ranks in FEN");
+        assert_eq!(FenError::TooManyPieces { rank: 3 }.to_string(), "Too many pieces in rank");
+        assert_eq!(FenError::InvalidPieceChar { rank: 3, ch: 'x' }.to_string(), "Invalid piece character");
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 x - - 0 1").unwrap_err(), FenError::BadSideToMove);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w KX - 0 1").unwrap_err(), FenError::BadCastling);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - e9 0 1").unwrap_err(), FenError::BadEnPassant);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - x 1").unwrap_err(), FenError::BadHalfmove);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 x").unwrap_err(), FenError::BadFullmove);
+        assert_eq!(FenError::BadSideToMove.to_string(), "Invalid side to move");
+        assert_eq!("4k3/8/8/8/8/8/8/4K3 x - - 0 1".parse::<Board>().unwrap_err(), FenError::BadSideToMove);
+        assert!(crate::testing::KIWIPETE.parse::<Board>().is_ok());
+    }
+
+    #[test]
+    fn test_from_fen_strict() {
+        assert!(Board::from_fen_strict(crate::testing::STARTPOS).is_ok());
+        assert!(Board::from_fen_strict(crate::testing::KIWIPETE).is_ok());
+        assert!(Board::from_fen_strict("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2").is_ok());
+
+        let err = |fen| Board::from_fen_strict(fen).unwrap_err();
+        assert_eq!(err("8/8/8/8/8/8/8/4K3 w - - 0 1"), FenError::BadKingCount);
+        assert_eq!(err("4k3/8/8/8/8/8/8/3KK3 w - - 0 1"), FenError::BadKingCount);
+        assert_eq!(err("4k3/8/8/8/8/8/8/P3K3 w - - 0 1"), FenError::PawnOnBackRank);
+        assert_eq!(err("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1"), FenError::OpponentInCheck);
+        assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w - e6 0 1"), FenError::BadEnPassant);
+        assert_eq!(err("4k3/8/8/4p3/8/8/8/4K3 w - e3 0 1"), FenError::BadEnPassant);
+        assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w K - 0 1"), FenError::BadCastling);
+
+        // Malformed placement
+        assert_eq!(err("4k4/8/8/8/8/8/8/4K3 w - - 0 1"), FenError::TooManyPieces { rank: 8 });
+        assert_eq!(err("4k3/8/8/8/8/8/8/4K2 w - - 0 1"), FenError::TooFewSquares { rank: 1 });
+        assert_eq!(err("4k3/8/7/8/8/8/8/4K3 w - - 0 1"), FenError::TooFewSquares { rank: 6 });
+        assert_eq!(err("4k3/8/8/8/8/8/4K3 w - - 0 1"), FenError::TooFewRanks);
+        let digits = format!("4k3/8/8/8/8/8/8/4K3{} w - - 0 1", "8".repeat(40));
+        assert_eq!(err(&digits), FenError::TooManyPieces { rank: 1 });
+        assert!(Board::from_fen(&digits).is_ok());
+
+        // The lenient parser still accepts these
+        assert!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w K e6 0 1").is_ok());
+        assert!(Board::from_fen("4k4/8/8/8/8/8/8/4K3 w - - 0 1").is_ok());
     }
 
     #[test]
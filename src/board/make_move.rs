@@ -1,25 +1,47 @@
 //! Make and unmake move logic.
 
+use core::fmt;
 use super::Board;
 use super::zobrist::ZOBRIST;
+use super::accumulator::{Accumulator, NoAccumulator};
 use crate::bitboard::Bitboard;
 use crate::types::{Square, Piece, Color, CastleRights};
 use crate::movegen::{Move, MoveFlag};
 
 /// State that needs to be saved for unmaking a move.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct UndoInfo {
     pub castling: CastleRights,
     pub ep_square: Option<Square>,
     pub halfmove_clock: u8,
     pub hash: u64,
+    pub pawn_hash: u64,
     pub checkers: Bitboard,
+    pub pinned: Bitboard,
     pub captured: Option<Piece>,
 }
 
+/// Error returned by [`Board::try_make_move`] for a move that is not legal
+/// in the current position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalMove(pub Move);
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "illegal move {}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IllegalMove {}
+
 impl Board {
     /// Make a move and return a new board (copy-make pattern).
     /// This is the recommended method for search.
+    ///
+    /// `mv` must be legal in this position (see [`Board::try_make_move`]);
+    /// anything else corrupts the board. Debug builds panic on a move from
+    /// an empty square.
     #[inline]
     pub fn make_move_new(&self, mv: Move) -> Board {
         let mut result = *self;
@@ -31,14 +53,13 @@ impl Board {
         let them = !us;
 
         // Find the moving piece
+        debug_assert!(self.piece_at(from).is_some(), "move {mv} from empty square");
         let piece = self.piece_at(from).map(|(p, _)| p).unwrap_or(Piece::Pawn);
 
         // === Zobrist hash updates ===
         
         // XOR out old en passant
-        if let Some(ep) = self.ep_square {
-            result.hash ^= ZOBRIST.ep_file(ep.file());
-        }
+        result.hash ^= self.ep_hash();
         result.ep_square = None;
         
         // XOR out old castling rights
@@ -49,11 +70,15 @@ impl Board {
                 // Move piece: XOR out from, XOR in to
                 result.hash ^= ZOBRIST.piece_square(piece, us, from);
                 result.hash ^= ZOBRIST.piece_square(piece, us, to);
+                result.pawn_hash ^= ZOBRIST.pawn_king_square(piece, us, from)
+                    ^ ZOBRIST.pawn_king_square(piece, us, to);
                 result.move_piece_fast(from, to, piece, us);
             }
             MoveFlag::DoublePawnPush => {
                 result.hash ^= ZOBRIST.piece_square(piece, us, from);
                 result.hash ^= ZOBRIST.piece_square(piece, us, to);
+                result.pawn_hash ^= ZOBRIST.piece_square(Piece::Pawn, us, from)
+                    ^ ZOBRIST.piece_square(Piece::Pawn, us, to);
                 result.move_piece_fast(from, to, piece, us);
                 let ep = if us == Color::White {
                     unsafe { Square::from_index_unchecked(to.index() - 8) }
@@ -61,17 +86,22 @@ impl Board {
                     unsafe { Square::from_index_unchecked(to.index() + 8) }
                 };
                 result.ep_square = Some(ep);
-                result.hash ^= ZOBRIST.ep_file(ep.file());
+                if result.ep_capturable_by(ep, them) {
+                    result.hash ^= ZOBRIST.ep_file(ep.file());
+                }
             }
             MoveFlag::Capture => {
                 // Remove captured piece
                 if let Some((cap_piece, _)) = self.piece_at(to) {
                     result.hash ^= ZOBRIST.piece_square(cap_piece, them, to);
+                    result.pawn_hash ^= ZOBRIST.pawn_king_square(cap_piece, them, to);
                     result.remove_piece_fast(to, cap_piece, them);
                 }
                 // Move capturing piece
                 result.hash ^= ZOBRIST.piece_square(piece, us, from);
                 result.hash ^= ZOBRIST.piece_square(piece, us, to);
+                result.pawn_hash ^= ZOBRIST.pawn_king_square(piece, us, from)
+                    ^ ZOBRIST.pawn_king_square(piece, us, to);
                 result.move_piece_fast(from, to, piece, us);
             }
             MoveFlag::EnPassant => {
@@ -86,42 +116,26 @@ impl Board {
                 // Move pawn
                 result.hash ^= ZOBRIST.piece_square(Piece::Pawn, us, from);
                 result.hash ^= ZOBRIST.piece_square(Piece::Pawn, us, to);
+                result.pawn_hash ^= ZOBRIST.piece_square(Piece::Pawn, them, cap_sq)
+                    ^ ZOBRIST.piece_square(Piece::Pawn, us, from)
+                    ^ ZOBRIST.piece_square(Piece::Pawn, us, to);
                 result.move_piece_fast(from, to, Piece::Pawn, us);
             }
-            MoveFlag::KingCastle => {
-                // Move king
+            MoveFlag::KingCastle | MoveFlag::QueenCastle => {
+                let (rook_from, rook_to) = self.castle_rook_move(us, flag == MoveFlag::KingCastle);
                 result.hash ^= ZOBRIST.piece_square(Piece::King, us, from);
                 result.hash ^= ZOBRIST.piece_square(Piece::King, us, to);
-                result.move_piece_fast(from, to, Piece::King, us);
-                // Move rook
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::H1, Square::F1)
-                } else {
-                    (Square::H8, Square::F8)
-                };
                 result.hash ^= ZOBRIST.piece_square(Piece::Rook, us, rook_from);
                 result.hash ^= ZOBRIST.piece_square(Piece::Rook, us, rook_to);
-                result.move_piece_fast(rook_from, rook_to, Piece::Rook, us);
-            }
-            MoveFlag::QueenCastle => {
-                // Move king
-                result.hash ^= ZOBRIST.piece_square(Piece::King, us, from);
-                result.hash ^= ZOBRIST.piece_square(Piece::King, us, to);
-                result.move_piece_fast(from, to, Piece::King, us);
-                // Move rook
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::A1, Square::D1)
-                } else {
-                    (Square::A8, Square::D8)
-                };
-                result.hash ^= ZOBRIST.piece_square(Piece::Rook, us, rook_from);
-                result.hash ^= ZOBRIST.piece_square(Piece::Rook, us, rook_to);
-                result.move_piece_fast(rook_from, rook_to, Piece::Rook, us);
+                result.pawn_hash ^= ZOBRIST.piece_square(Piece::King, us, from)
+                    ^ ZOBRIST.piece_square(Piece::King, us, to);
+                result.castle_pieces_fast(us, from, to, rook_from, rook_to);
             }
             _ if flag.is_promotion() => {
                 let promo_piece = flag.promotion_piece().unwrap();
                 // Remove pawn
                 result.hash ^= ZOBRIST.piece_square(Piece::Pawn, us, from);
+                result.pawn_hash ^= ZOBRIST.piece_square(Piece::Pawn, us, from);
                 result.remove_piece_fast(from, Piece::Pawn, us);
                 // If capture, remove captured piece
                 if flag.is_capture() {
@@ -138,11 +152,20 @@ impl Board {
         }
 
         // Update castling rights
-        let new_castling = result.castling.remove(CastleRights::update_mask(from))
-                                          .remove(CastleRights::update_mask(to));
+        let new_castling = result.castling.remove(self.castle_update_mask(from, to));
         result.castling = new_castling;
         result.hash ^= ZOBRIST.castling(new_castling);
 
+        // Update move counters
+        if piece == Piece::Pawn || flag.is_capture() {
+            result.halfmove_clock = 0;
+        } else {
+            result.halfmove_clock = self.halfmove_clock.saturating_add(1);
+        }
+        if us == Color::Black {
+            result.fullmove_number += 1;
+        }
+
         // Switch side
         result.turn = them;
         result.hash ^= ZOBRIST.side();
@@ -154,13 +177,37 @@ impl Board {
     }
     /// Make a move on the board.
     /// Returns undo information for unmaking the move.
+    ///
+    /// `mv` must be legal in this position (see [`Board::try_make_move`]);
+    /// anything else corrupts the board. Debug builds panic on a move from
+    /// an empty square.
+    #[inline]
     pub fn make_move(&mut self, mv: Move) -> UndoInfo {
+        self.make_move_with(mv, &mut NoAccumulator)
+    }
+
+    /// Make `mv` only if it is legal; otherwise leave the board untouched.
+    ///
+    /// Safe entry point for user input, unlike [`Board::make_move`] which
+    /// trusts its argument.
+    #[inline]
+    pub fn try_make_move(&mut self, mv: Move) -> Result<UndoInfo, IllegalMove> {
+        if !self.is_legal(mv) {
+            return Err(IllegalMove(mv));
+        }
+        Ok(self.make_move(mv))
+    }
+
+    /// Make a move, reporting every piece placement change to `acc`.
+    pub fn make_move_with<A: Accumulator>(&mut self, mv: Move, acc: &mut A) -> UndoInfo {
         let undo = UndoInfo {
             castling: self.castling,
             ep_square: self.ep_square,
             halfmove_clock: self.halfmove_clock,
             hash: self.hash,
+            pawn_hash: self.pawn_hash,
             checkers: self.checkers,
+            pinned: self.pinned,
             captured: None,
         };
 
@@ -169,14 +216,14 @@ impl Board {
         let flag = mv.flag();
         let us = self.turn;
         let them = !us;
+        let castle_clear = self.castle_update_mask(from, to);
 
         // Find the moving piece
+        debug_assert!(self.piece_at(from).is_some(), "move {mv} from empty square");
         let piece = self.piece_at(from).map(|(p, _)| p).unwrap_or(Piece::Pawn);
 
         // Handle en passant hash
-        if let Some(ep) = self.ep_square {
-            self.hash ^= ZOBRIST.ep_file(ep.file());
-        }
+        self.hash ^= self.ep_hash();
         self.ep_square = None;
 
         // Handle castling rights hash
@@ -188,6 +235,7 @@ impl Board {
         match flag {
             MoveFlag::Quiet | MoveFlag::DoublePawnPush => {
                 self.move_piece(from, to, piece, us);
+                acc.on_move(piece, us, from, to);
                 
                 if flag == MoveFlag::DoublePawnPush {
                     // Set en passant square
@@ -197,7 +245,9 @@ impl Board {
                         unsafe { Square::from_index_unchecked(to.index() + 8) }
                     };
                     self.ep_square = Some(ep);
-                    self.hash ^= ZOBRIST.ep_file(ep.file());
+                    if self.ep_capturable_by(ep, them) {
+                        self.hash ^= ZOBRIST.ep_file(ep.file());
+                    }
                 }
             }
             MoveFlag::Capture => {
@@ -205,8 +255,10 @@ impl Board {
                 if let Some((cap_piece, _)) = self.piece_at(to) {
                     captured = Some(cap_piece);
                     self.remove_piece(to, cap_piece, them);
+                    acc.on_remove(cap_piece, them, to);
                 }
                 self.move_piece(from, to, piece, us);
+                acc.on_move(piece, us, from, to);
             }
             MoveFlag::EnPassant => {
                 let cap_sq = if us == Color::White {
@@ -216,60 +268,48 @@ impl Board {
                 };
                 captured = Some(Piece::Pawn);
                 self.remove_piece(cap_sq, Piece::Pawn, them);
+                acc.on_remove(Piece::Pawn, them, cap_sq);
                 self.move_piece(from, to, Piece::Pawn, us);
+                acc.on_move(Piece::Pawn, us, from, to);
             }
-            MoveFlag::KingCastle => {
-                // Move king
-                self.move_piece(from, to, Piece::King, us);
-                // Move rook
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::H1, Square::F1)
-                } else {
-                    (Square::H8, Square::F8)
-                };
-                self.move_piece(rook_from, rook_to, Piece::Rook, us);
-            }
-            MoveFlag::QueenCastle => {
-                // Move king
-                self.move_piece(from, to, Piece::King, us);
-                // Move rook
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::A1, Square::D1)
-                } else {
-                    (Square::A8, Square::D8)
-                };
-                self.move_piece(rook_from, rook_to, Piece::Rook, us);
+            MoveFlag::KingCastle | MoveFlag::QueenCastle => {
+                let (rook_from, rook_to) = self.castle_rook_move(us, flag == MoveFlag::KingCastle);
+                self.castle_pieces(us, from, to, rook_from, rook_to);
+                acc.on_move(Piece::King, us, from, to);
+                acc.on_move(Piece::Rook, us, rook_from, rook_to);
             }
             _ if flag.is_promotion() => {
                 let promo_piece = flag.promotion_piece().unwrap();
                 
                 // Remove pawn
                 self.remove_piece(from, Piece::Pawn, us);
+                acc.on_remove(Piece::Pawn, us, from);
                 
                 // Capture if applicable
                 if flag.is_capture() {
                     if let Some((cap_piece, _)) = self.piece_at(to) {
                         captured = Some(cap_piece);
                         self.remove_piece(to, cap_piece, them);
+                        acc.on_remove(cap_piece, them, to);
                     }
                 }
                 
                 // Add promoted piece
                 self.add_piece(to, promo_piece, us);
+                acc.on_add(promo_piece, us, to);
             }
             _ => {}
         }
 
         // Update castling rights
-        self.castling = self.castling.remove(CastleRights::update_mask(from));
-        self.castling = self.castling.remove(CastleRights::update_mask(to));
+        self.castling = self.castling.remove(castle_clear);
         self.hash ^= ZOBRIST.castling(self.castling);
 
         // Update halfmove clock
         if piece == Piece::Pawn || captured.is_some() {
             self.halfmove_clock = 0;
         } else {
-            self.halfmove_clock += 1;
+            self.halfmove_clock = self.halfmove_clock.saturating_add(1);
         }
 
         // Update fullmove number
@@ -288,7 +328,11 @@ impl Board {
     }
 
     /// Make a move without updating checkers (faster for perft).
-    /// The checkers bitboard will be invalid after this call.
+    /// The checkers and pinned bitboards will be invalid after this call.
+    ///
+    /// `mv` must be legal in this position (see [`Board::try_make_move`]);
+    /// anything else corrupts the board. Debug builds panic on a move from
+    /// an empty square.
     #[inline(always)]
     pub fn make_move_fast(&mut self, mv: &Move) -> UndoInfo {
         let undo = UndoInfo {
@@ -296,7 +340,9 @@ impl Board {
             ep_square: self.ep_square,
             halfmove_clock: self.halfmove_clock,
             hash: self.hash,
+            pawn_hash: self.pawn_hash,
             checkers: self.checkers,
+            pinned: self.pinned,
             captured: None,
         };
 
@@ -305,8 +351,10 @@ impl Board {
         let flag = mv.flag();
         let us = self.turn;
         let them = !us;
+        let castle_clear = self.castle_update_mask(from, to);
 
         // Find the moving piece
+        debug_assert!(self.piece_at(from).is_some(), "move {mv} from empty square");
         let piece = self.piece_at(from).map(|(p, _)| p).unwrap_or(Piece::Pawn);
 
         // Clear en passant
@@ -345,23 +393,9 @@ impl Board {
                 self.remove_piece_fast(cap_sq, Piece::Pawn, them);
                 self.move_piece_fast(from, to, Piece::Pawn, us);
             }
-            MoveFlag::KingCastle => {
-                self.move_piece_fast(from, to, Piece::King, us);
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::H1, Square::F1)
-                } else {
-                    (Square::H8, Square::F8)
-                };
-                self.move_piece_fast(rook_from, rook_to, Piece::Rook, us);
-            }
-            MoveFlag::QueenCastle => {
-                self.move_piece_fast(from, to, Piece::King, us);
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::A1, Square::D1)
-                } else {
-                    (Square::A8, Square::D8)
-                };
-                self.move_piece_fast(rook_from, rook_to, Piece::Rook, us);
+            MoveFlag::KingCastle | MoveFlag::QueenCastle => {
+                let (rook_from, rook_to) = self.castle_rook_move(us, flag == MoveFlag::KingCastle);
+                self.castle_pieces_fast(us, from, to, rook_from, rook_to);
             }
             _ if flag.is_promotion() => {
                 let promo_piece = flag.promotion_piece().unwrap();
@@ -378,8 +412,7 @@ impl Board {
         }
 
         // Update castling rights
-        self.castling = self.castling.remove(CastleRights::update_mask(from));
-        self.castling = self.castling.remove(CastleRights::update_mask(to));
+        self.castling = self.castling.remove(castle_clear);
 
         // Switch side
         self.turn = them;
@@ -388,7 +421,13 @@ impl Board {
     }
 
     /// Unmake a move on the board.
+    #[inline]
     pub fn unmake_move(&mut self, mv: Move, undo: UndoInfo) {
+        self.unmake_move_with(mv, undo, &mut NoAccumulator);
+    }
+
+    /// Unmake a move, reporting every piece placement change to `acc`.
+    pub fn unmake_move_with<A: Accumulator>(&mut self, mv: Move, undo: UndoInfo, acc: &mut A) {
         let from = mv.from();
         let to = mv.to();
         let flag = mv.flag();
@@ -408,46 +447,42 @@ impl Board {
         match flag {
             MoveFlag::Quiet | MoveFlag::DoublePawnPush => {
                 self.move_piece(to, from, piece, us);
+                acc.on_move(piece, us, to, from);
             }
             MoveFlag::Capture => {
                 self.move_piece(to, from, piece, us);
+                acc.on_move(piece, us, to, from);
                 if let Some(cap_piece) = undo.captured {
                     self.add_piece(to, cap_piece, them);
+                    acc.on_add(cap_piece, them, to);
                 }
             }
             MoveFlag::EnPassant => {
                 self.move_piece(to, from, Piece::Pawn, us);
+                acc.on_move(Piece::Pawn, us, to, from);
                 let cap_sq = if us == Color::White {
                     unsafe { Square::from_index_unchecked(to.index() - 8) }
                 } else {
                     unsafe { Square::from_index_unchecked(to.index() + 8) }
                 };
                 self.add_piece(cap_sq, Piece::Pawn, them);
+                acc.on_add(Piece::Pawn, them, cap_sq);
             }
-            MoveFlag::KingCastle => {
-                self.move_piece(to, from, Piece::King, us);
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::H1, Square::F1)
-                } else {
-                    (Square::H8, Square::F8)
-                };
-                self.move_piece(rook_to, rook_from, Piece::Rook, us);
-            }
-            MoveFlag::QueenCastle => {
-                self.move_piece(to, from, Piece::King, us);
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::A1, Square::D1)
-                } else {
-                    (Square::A8, Square::D8)
-                };
-                self.move_piece(rook_to, rook_from, Piece::Rook, us);
+            MoveFlag::KingCastle | MoveFlag::QueenCastle => {
+                let (rook_from, rook_to) = self.castle_rook_move(us, flag == MoveFlag::KingCastle);
+                self.castle_pieces(us, to, from, rook_to, rook_from);
+                acc.on_move(Piece::King, us, to, from);
+                acc.on_move(Piece::Rook, us, rook_to, rook_from);
             }
             _ if flag.is_promotion() => {
                 let promo_piece = flag.promotion_piece().unwrap();
                 self.remove_piece(to, promo_piece, us);
+                acc.on_remove(promo_piece, us, to);
                 self.add_piece(from, Piece::Pawn, us);
+                acc.on_add(Piece::Pawn, us, from);
                 if let Some(cap_piece) = undo.captured {
                     self.add_piece(to, cap_piece, them);
+                    acc.on_add(cap_piece, them, to);
                 }
             }
             _ => {}
@@ -458,7 +493,9 @@ impl Board {
         self.ep_square = undo.ep_square;
         self.halfmove_clock = undo.halfmove_clock;
         self.hash = undo.hash;
+        self.pawn_hash = undo.pawn_hash;
         self.checkers = undo.checkers;
+        self.pinned = undo.pinned;
         
         // Fullmove number
         if us == Color::Black {
@@ -504,23 +541,9 @@ impl Board {
                 };
                 self.add_piece_fast(cap_sq, Piece::Pawn, them);
             }
-            MoveFlag::KingCastle => {
-                self.move_piece_fast(to, from, Piece::King, us);
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::H1, Square::F1)
-                } else {
-                    (Square::H8, Square::F8)
-                };
-                self.move_piece_fast(rook_to, rook_from, Piece::Rook, us);
-            }
-            MoveFlag::QueenCastle => {
-                self.move_piece_fast(to, from, Piece::King, us);
-                let (rook_from, rook_to) = if us == Color::White {
-                    (Square::A1, Square::D1)
-                } else {
-                    (Square::A8, Square::D8)
-                };
-                self.move_piece_fast(rook_to, rook_from, Piece::Rook, us);
+            MoveFlag::KingCastle | MoveFlag::QueenCastle => {
+                let (rook_from, rook_to) = self.castle_rook_move(us, flag == MoveFlag::KingCastle);
+                self.castle_pieces_fast(us, to, from, rook_to, rook_from);
             }
             _ if flag.is_promotion() => {
                 let promo_piece = flag.promotion_piece().unwrap();
@@ -537,6 +560,59 @@ impl Board {
         self.castling = undo.castling;
         self.ep_square = undo.ep_square;
         self.checkers = undo.checkers;
+        self.pinned = undo.pinned;
+    }
+
+    /// Get the rook's start and destination squares for castling.
+    #[inline(always)]
+    fn castle_rook_move(&self, us: Color, kingside: bool) -> (Square, Square) {
+        let (_, rook_to) = self.castle_destinations(us, kingside);
+        (self.castle_rook_square(us, kingside), rook_to)
+    }
+
+    /// Castling rights lost by a move from `from` to `to`.
+    /// Must be called before the move is made.
+    #[inline(always)]
+    fn castle_update_mask(&self, from: Square, to: Square) -> CastleRights {
+        if !self.chess960 {
+            return CastleRights::update_mask(from) | CastleRights::update_mask(to);
+        }
+
+        let mut mask = CastleRights::NONE;
+        for (i, &rook) in self.castle_rooks.iter().enumerate() {
+            if rook == from || rook == to {
+                mask |= CastleRights::from_bits(1 << i);
+            }
+        }
+        if from == self.king_square(self.turn) {
+            mask |= match self.turn {
+                Color::White => CastleRights::WHITE,
+                Color::Black => CastleRights::BLACK,
+            };
+        }
+        mask
+    }
+
+    /// Move king and rook for castling. Both are lifted before either is
+    /// placed, since in Chess960 their squares may overlap.
+    #[inline(always)]
+    fn castle_pieces_fast(&mut self, us: Color, king_from: Square, king_to: Square, rook_from: Square, rook_to: Square) {
+        self.remove_piece_fast(king_from, Piece::King, us);
+        self.remove_piece_fast(rook_from, Piece::Rook, us);
+        self.add_piece_fast(king_to, Piece::King, us);
+        self.add_piece_fast(rook_to, Piece::Rook, us);
+    }
+
+    /// Same as `castle_pieces_fast`, keeping the hash in sync.
+    #[inline(always)]
+    fn castle_pieces(&mut self, us: Color, king_from: Square, king_to: Square, rook_from: Square, rook_to: Square) {
+        self.castle_pieces_fast(us, king_from, king_to, rook_from, rook_to);
+        self.hash ^= ZOBRIST.piece_square(Piece::King, us, king_from)
+            ^ ZOBRIST.piece_square(Piece::King, us, king_to)
+            ^ ZOBRIST.piece_square(Piece::Rook, us, rook_from)
+            ^ ZOBRIST.piece_square(Piece::Rook, us, rook_to);
+        self.pawn_hash ^= ZOBRIST.piece_square(Piece::King, us, king_from)
+            ^ ZOBRIST.piece_square(Piece::King, us, king_to);
     }
 
     /// Make a null move (pass the turn without making any move).
@@ -547,6 +623,7 @@ impl Board {
         let mut result = *self;
         
         // Clear en passant
+        result.hash ^= self.ep_hash();
         result.ep_square = None;
         
         // Switch side
@@ -560,12 +637,58 @@ impl Board {
         
         result
     }
+
+    /// Drop a piece of the side to move onto an empty square and pass the turn.
+    /// The caller is responsible for pocket bookkeeping and drop legality.
+    #[cfg(feature = "variants")]
+    pub fn drop_piece(&mut self, piece: Piece, sq: Square) {
+        debug_assert!(self.piece_at(sq).is_none());
+
+        self.hash ^= self.ep_hash();
+        self.ep_square = None;
+
+        self.add_piece(sq, piece, self.turn);
+        self.halfmove_clock = 0;
+        if self.turn == Color::Black {
+            self.fullmove_number += 1;
+        }
+
+        self.turn = !self.turn;
+        self.hash ^= ZOBRIST.side();
+        self.update_checkers();
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "from empty square")]
+    fn test_make_move_empty_square() {
+        Board::startpos().make_move(Move::new(Square::E4, Square::E5, MoveFlag::Quiet));
+    }
+
+    #[test]
+    fn test_try_make_move() {
+        let mut board = Board::startpos();
+        let before = board;
+
+        // Empty from-square, and a knight move that is not a knight move
+        for mv in [Move::new(Square::E4, Square::E5, MoveFlag::Quiet), Move::new(Square::G1, Square::G3, MoveFlag::Quiet)] {
+            assert_eq!(board.try_make_move(mv).unwrap_err(), IllegalMove(mv));
+            assert_eq!(board, before);
+            assert_eq!(board.hash(), before.hash());
+        }
+
+        let mv = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
+        let undo = board.try_make_move(mv).unwrap();
+        assert_eq!(board, before.make_move_new(mv));
+        board.unmake_move(mv, undo);
+        assert_eq!(board, before);
+    }
+
     #[test]
     fn test_make_unmake_quiet() {
         let mut board = Board::startpos();
@@ -584,5 +707,61 @@ mod tests {
         assert_eq!(board.to_fen(), initial_fen);
         assert_eq!(board.hash(), initial_hash);
     }
+
+    #[test]
+    fn test_make_move_new_counters() {
+        // Shuffle knights back and forth: ten quiet moves
+        let shuffle = [
+            Move::new(Square::G1, Square::F3, MoveFlag::Quiet),
+            Move::new(Square::G8, Square::F6, MoveFlag::Quiet),
+            Move::new(Square::F3, Square::G1, MoveFlag::Quiet),
+            Move::new(Square::F6, Square::G8, MoveFlag::Quiet),
+        ];
+        let mut board = Board::startpos();
+        for mv in shuffle.iter().cycle().take(10) {
+            board = board.make_move_new(*mv);
+        }
+        assert_eq!(board.halfmove_clock(), 10);
+        assert_eq!(board.fullmove_number(), 6);
+
+        // A pawn move resets the clock
+        let board = board.make_move_new(Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush));
+        assert_eq!(board.halfmove_clock(), 0);
+        assert_eq!(board.fullmove_number(), 6);
+    }
+
+    /// Tracks white material minus black material.
+    struct Material(i32);
+
+    impl Material {
+        fn value(piece: Piece, color: Color) -> i32 {
+            let v = crate::types::PIECE_VALUES[piece.index()];
+            if color == Color::White { v } else { -v }
+        }
+    }
+
+    impl Accumulator for Material {
+        fn on_add(&mut self, piece: Piece, color: Color, _sq: Square) {
+            self.0 += Material::value(piece, color);
+        }
+
+        fn on_remove(&mut self, piece: Piece, color: Color, _sq: Square) {
+            self.0 -= Material::value(piece, color);
+        }
+    }
+
+    #[test]
+    fn test_accumulator_capture() {
+        let mut board = Board::from_fen("4k3/8/8/3r4/4N3/8/8/4K3 b - - 0 1").unwrap();
+        let mut material = Material(300 - 500);
+
+        // Rook takes knight
+        let mv = Move::new(Square::D5, Square::E4, MoveFlag::Capture);
+        let undo = board.make_move_with(mv, &mut material);
+        assert_eq!(material.0, -500);
+
+        board.unmake_move_with(mv, undo, &mut material);
+        assert_eq!(material.0, 300 - 500);
+    }
 }
 
@@ -3,12 +3,22 @@
 mod fen;
 mod zobrist;
 mod make_move;
+mod accumulator;
+mod display;
+mod pawns;
+mod nnue;
 
-
-pub use zobrist::ZOBRIST;
+pub use fen::FenError;
+pub use zobrist::{Zobrist, ZOBRIST};
+pub use accumulator::{Accumulator, NoAccumulator};
+pub use make_move::{UndoInfo, IllegalMove};
+pub use nnue::{halfkp_index, HALFKP_FEATURES};
 
 use crate::bitboard::Bitboard;
-use crate::types::{Square, Piece, Color, CastleRights};
+use crate::types::{Square, File, Piece, Color, CastleRights};
+
+/// Rook squares for standard castling: H1, A1, H8, A8.
+const STANDARD_CASTLE_ROOKS: [Square; 4] = [Square::H1, Square::A1, Square::H8, Square::A8];
 
 /// Chess board state.
 #[derive(Clone, Copy)]
@@ -29,8 +39,16 @@ pub struct Board {
     fullmove_number: u16,
     /// Zobrist hash.
     hash: u64,
+    /// Zobrist hash of pawns and kings only.
+    pawn_hash: u64,
     /// Cached checkers bitboard.
     checkers: Bitboard,
+    /// Cached pinned pieces of the side to move.
+    pinned: Bitboard,
+    /// Castling rook squares, indexed like the `CastleRights` bits.
+    castle_rooks: [Square; 4],
+    /// Whether castling follows Chess960 rules.
+    chess960: bool,
 }
 
 impl Board {
@@ -45,7 +63,11 @@ impl Board {
             halfmove_clock: 0,
             fullmove_number: 1,
             hash: 0,
+            pawn_hash: 0,
             checkers: Bitboard::EMPTY,
+            pinned: Bitboard::EMPTY,
+            castle_rooks: STANDARD_CASTLE_ROOKS,
+            chess960: false,
         }
     }
 
@@ -96,20 +118,70 @@ impl Board {
         self.fullmove_number
     }
 
+    /// The en passant square if a pawn of the side to move attacks it.
+    /// Only such a square counts towards equality and the hash.
+    #[inline]
+    pub(crate) fn capturable_ep_square(&self) -> Option<Square> {
+        self.ep_square.filter(|&ep| self.ep_capturable_by(ep, self.turn))
+    }
+
+    /// Whether a pawn of `color` attacks the en passant square `ep`.
+    #[inline(always)]
+    pub(crate) fn ep_capturable_by(&self, ep: Square, color: Color) -> bool {
+        (crate::attacks::pawn_attacks(!color, ep) & self.piece_color_bb(Piece::Pawn, color)).any()
+    }
+
+    /// Zobrist key of the capturable en passant file, or 0.
+    #[inline(always)]
+    pub(crate) fn ep_hash(&self) -> u64 {
+        self.capturable_ep_square().map_or(0, |ep| ZOBRIST.ep_file(ep.file()))
+    }
+
     /// Get the Zobrist hash.
+    ///
+    /// Covers pieces, side to move, castling rights and the en passant file
+    /// when a pawn can capture there, but not the move counters, so
+    /// repeated positions hash equal exactly when they compare equal.
     #[inline(always)]
     pub fn hash(&self) -> u64 {
         self.hash
     }
 
-    /// Get a pawn structure hash (for correction history).
-    /// This combines info about pawn positions for both sides.
-    #[inline]
+    /// Get the Zobrist hash of pawns and kings only, for pawn structure
+    /// caches and correction history. Maintained incrementally.
+    #[inline(always)]
     pub fn pawn_hash(&self) -> u64 {
-        // Simple pawn hash: XOR of pawn bitboard bits with a mixing constant
-        let white_pawns = self.piece_color_bb(Piece::Pawn, Color::White).0;
-        let black_pawns = self.piece_color_bb(Piece::Pawn, Color::Black).0;
-        white_pawns.wrapping_mul(0x9E3779B97F4A7C15) ^ black_pawns.wrapping_mul(0xC6A4A7935BD1E995)
+        self.pawn_hash
+    }
+
+    /// Check if castling follows Chess960 rules.
+    #[inline(always)]
+    pub fn is_chess960(&self) -> bool {
+        self.chess960
+    }
+
+    /// Switch Chess960 castling on or off.
+    /// Turning it off resets the castling rooks to the corners.
+    pub fn set_chess960(&mut self, chess960: bool) {
+        self.chess960 = chess960;
+        if !chess960 {
+            self.castle_rooks = STANDARD_CASTLE_ROOKS;
+        }
+    }
+
+    /// Get the starting square of the rook used to castle on the given side.
+    #[inline(always)]
+    pub fn castle_rook_square(&self, color: Color, kingside: bool) -> Square {
+        self.castle_rooks[castle_index(color, kingside)]
+    }
+
+    /// Get the king and rook destinations for castling on the given side.
+    /// These are the same in Chess960 and standard chess.
+    #[inline(always)]
+    pub fn castle_destinations(&self, color: Color, kingside: bool) -> (Square, Square) {
+        let rank = color.back_rank();
+        let (king, rook) = if kingside { (File::G, File::F) } else { (File::C, File::D) };
+        (Square::from_file_rank(king, rank), Square::from_file_rank(rook, rank))
     }
 
     /// Get bitboard of pieces that are giving check.
@@ -118,6 +190,12 @@ impl Board {
         self.checkers
     }
 
+    /// Get bitboard of the side to move's pieces pinned to its king.
+    #[inline(always)]
+    pub fn pinned(&self) -> Bitboard {
+        self.pinned
+    }
+
     /// Check if the side to move is in check.
     #[inline(always)]
     pub fn in_check(&self) -> bool {
@@ -154,6 +232,33 @@ impl Board {
         self.pieces[piece.index()] & self.colors[color.index()]
     }
 
+    /// Number of pieces of the given type and color.
+    #[inline(always)]
+    pub fn piece_count(&self, piece: Piece, color: Color) -> u32 {
+        self.piece_color_bb(piece, color).count()
+    }
+
+    /// Total material of `color` in centipawns, using [`PIECE_VALUES`](crate::PIECE_VALUES).
+    #[inline]
+    pub fn material_count(&self, color: Color) -> i32 {
+        Piece::ALL[..5]
+            .iter()
+            .map(|&p| self.piece_count(p, color) as i32 * crate::types::PIECE_VALUES[p.index()])
+            .sum()
+    }
+
+    /// Game phase from 24 (all pieces on, midgame) down to 0 (bare pawns),
+    /// using [`PHASE_WEIGHTS`](crate::PHASE_WEIGHTS) over both sides. Extra
+    /// promoted pieces are clamped to 24.
+    #[inline]
+    pub fn game_phase(&self) -> u8 {
+        let phase: u32 = Piece::ALL
+            .iter()
+            .map(|&p| self.piece_bb(p).count() * crate::types::PHASE_WEIGHTS[p.index()] as u32)
+            .sum();
+        phase.min(24) as u8
+    }
+
     /// Get our pieces bitboard.
     #[inline(always)]
     pub fn us(&self) -> Bitboard {
@@ -174,7 +279,6 @@ impl Board {
         unsafe { king_bb.lsb_unchecked() }
     }
 
-    /// Get the piece at a square.
     /// Get the piece at a square.
     pub fn piece_at(&self, sq: Square) -> Option<(Piece, Color)> {
         let sq_bb = Bitboard::from_square(sq);
@@ -196,7 +300,101 @@ impl Board {
         if (self.pieces[2] & sq_bb).any() { return Some((Piece::Bishop, color)); }
         if (self.pieces[3] & sq_bb).any() { return Some((Piece::Rook, color)); }
         if (self.pieces[4] & sq_bb).any() { return Some((Piece::Queen, color)); }
-        return Some((Piece::King, color));
+        Some((Piece::King, color))
+    }
+
+    /// Get the type of the piece making `mv` in this position.
+    /// Falls back to a pawn if the source square is empty.
+    #[inline]
+    pub fn move_piece_kind(&self, mv: crate::movegen::Move) -> Piece {
+        self.piece_at(mv.from()).map(|(p, _)| p).unwrap_or(Piece::Pawn)
+    }
+
+    /// Get the piece `mv` would capture, including the pawn taken en passant.
+    ///
+    /// Reads the board rather than the move flag, so it also works for moves
+    /// parsed without a position. Castling never captures, even when the
+    /// king lands on its own rook.
+    pub fn captured_piece(&self, mv: crate::movegen::Move) -> Option<Piece> {
+        if mv.is_castle() {
+            return None;
+        }
+        match self.piece_at(mv.to()) {
+            Some((piece, color)) if color != self.turn => Some(piece),
+            Some(_) => None,
+            None if mv.is_en_passant() => Some(Piece::Pawn),
+            None if self.ep_square == Some(mv.to()) && self.piece_color_bb(Piece::Pawn, self.turn).contains(mv.from()) => {
+                Some(Piece::Pawn)
+            }
+            None => None,
+        }
+    }
+
+    /// Check if `mv` captures a piece in this position (en passant included).
+    #[inline]
+    pub fn is_capture(&self, mv: crate::movegen::Move) -> bool {
+        self.captured_piece(mv).is_some()
+    }
+
+    /// Check if `mv` captures nothing in this position. Push promotions and
+    /// castling are quiet, matching [`Board::generate_quiets`].
+    #[inline]
+    pub fn is_quiet(&self, mv: crate::movegen::Move) -> bool {
+        !self.is_capture(mv)
+    }
+
+    /// Iterate over occupied squares from a1 to h8 with their piece and color.
+    #[inline]
+    pub fn piece_iter(&self) -> impl Iterator<Item = (Square, Piece, Color)> + '_ {
+        self.occupied().into_iter().map(move |sq| {
+            let (piece, color) = unsafe { self.piece_at(sq).unwrap_unchecked() };
+            (sq, piece, color)
+        })
+    }
+
+    /// Get a 64-square array indexed by [`Square::index`], a1 first.
+    pub fn to_mailbox(&self) -> [Option<(Piece, Color)>; 64] {
+        let mut squares = [None; 64];
+        for (sq, piece, color) in self.piece_iter() {
+            squares[sq.index() as usize] = Some((piece, color));
+        }
+        squares
+    }
+
+    /// Build a board from a 64-square array indexed by [`Square::index`].
+    ///
+    /// Bitboards, hashes and checkers are rebuilt and the position is
+    /// validated like [`BoardBuilder::build`]. Castling rooks are expected
+    /// on their standard squares.
+    pub fn from_mailbox(
+        squares: &[Option<(Piece, Color)>; 64],
+        turn: Color,
+        castling: CastleRights,
+        ep_square: Option<Square>,
+    ) -> Result<Board, FenError> {
+        let mut builder = BoardBuilder::new()
+            .side_to_move(turn)
+            .castling(castling)
+            .en_passant(ep_square);
+        for (sq, &contents) in Bitboard::UNIVERSE.into_iter().zip(squares) {
+            if let Some((piece, color)) = contents {
+                builder = builder.put(sq, piece, color);
+            }
+        }
+        builder.build()
+    }
+
+    /// Call `f` once for every occupied square with its piece and color.
+    /// Walks the piece bitboards directly instead of probing all 64 squares.
+    #[inline]
+    pub fn for_each_piece(&self, mut f: impl FnMut(Square, Piece, Color)) {
+        for piece in Piece::ALL {
+            for color in Color::ALL {
+                for sq in self.piece_color_bb(piece, color) {
+                    f(sq, piece, color);
+                }
+            }
+        }
     }
 
     /// Add a piece to the board.
@@ -206,6 +404,7 @@ impl Board {
         self.pieces[piece.index()] |= sq_bb;
         self.colors[color.index()] |= sq_bb;
         self.hash ^= ZOBRIST.piece_square(piece, color, sq);
+        self.pawn_hash ^= ZOBRIST.pawn_king_square(piece, color, sq);
     }
 
     /// Remove a piece from the board.
@@ -215,6 +414,52 @@ impl Board {
         self.pieces[piece.index()] &= !sq_bb;
         self.colors[color.index()] &= !sq_bb;
         self.hash ^= ZOBRIST.piece_square(piece, color, sq);
+        self.pawn_hash ^= ZOBRIST.pawn_king_square(piece, color, sq);
+    }
+
+    /// Flip the board vertically and swap the colors of every piece.
+    ///
+    /// Castling rights, castling rooks, the en passant square and the side to
+    /// move are mirrored too, so the result is the same position seen from the
+    /// other side. Move counters are kept.
+    pub fn mirror(&self) -> Board {
+        let mut board = *self;
+        for bb in board.pieces.iter_mut() {
+            *bb = bb.flip_vertical();
+        }
+        board.colors = [self.colors[1].flip_vertical(), self.colors[0].flip_vertical()];
+        board.turn = !self.turn;
+        let bits = self.castling.bits();
+        board.castling = CastleRights::from_bits((bits & 0b0011) << 2 | bits >> 2);
+        board.castle_rooks = [2, 3, 0, 1].map(|i| self.castle_rooks[i].flip_vertical());
+        board.ep_square = self.ep_square.map(Square::flip_vertical);
+        board.checkers = self.checkers.flip_vertical();
+        board.pinned = self.pinned.flip_vertical();
+        board.hash = board.recompute_hash();
+        board.pawn_hash = board.recompute_pawn_hash();
+        board
+    }
+
+    /// Overwrite a square, removing any previous occupant, and refresh the checkers and pins.
+    ///
+    /// Castling rights and the en passant square are left untouched. While the
+    /// side to move has no king the checkers and pins are empty.
+    pub fn set_square(&mut self, sq: Square, piece: Option<(Piece, Color)>) {
+        // Capturability of the en passant square may change
+        self.hash ^= self.ep_hash();
+        if let Some((old, color)) = self.piece_at(sq) {
+            self.remove_piece(sq, old, color);
+        }
+        if let Some((piece, color)) = piece {
+            self.add_piece(sq, piece, color);
+        }
+        self.hash ^= self.ep_hash();
+        if self.piece_color_bb(Piece::King, self.turn).exactly_one() {
+            self.update_checkers();
+        } else {
+            self.checkers = Bitboard::EMPTY;
+            self.pinned = Bitboard::EMPTY;
+        }
     }
 
     /// Move a piece on the board.
@@ -225,6 +470,8 @@ impl Board {
         self.colors[color.index()] ^= from_to;
         self.hash ^= ZOBRIST.piece_square(piece, color, from);
         self.hash ^= ZOBRIST.piece_square(piece, color, to);
+        self.pawn_hash ^= ZOBRIST.pawn_king_square(piece, color, from)
+            ^ ZOBRIST.pawn_king_square(piece, color, to);
     }
 
     /// Add a piece without updating hash (fast path for perft).
@@ -251,6 +498,29 @@ impl Board {
         self.colors[color.index()] ^= from_to;
     }
 
+    /// Check if neither side has enough material to deliver mate.
+    /// Covers K vs K, K+minor vs K, and K+B vs K+B with same-colored bishops.
+    pub fn has_insufficient_material(&self) -> bool {
+        let heavy = self.piece_bb(Piece::Pawn) | self.piece_bb(Piece::Rook) | self.piece_bb(Piece::Queen);
+        if heavy.any() {
+            return false;
+        }
+
+        let knights = self.piece_bb(Piece::Knight);
+        let bishops = self.piece_bb(Piece::Bishop);
+        let minors = (knights | bishops).count();
+        if minors <= 1 {
+            return true;
+        }
+
+        // One bishop each, on the same color complex
+        knights.is_empty()
+            && minors == 2
+            && (bishops & self.color_bb(Color::White)).exactly_one()
+            && ((bishops & Bitboard::LIGHT_SQUARES).is_empty()
+                || (bishops & Bitboard::DARK_SQUARES).is_empty())
+    }
+
     /// Compute attackers to a square.
     pub fn attackers_to(&self, sq: Square, occ: Bitboard) -> Bitboard {
         use crate::attacks::{pawn_attacks, knight_attacks, king_attacks, bishop_attacks, rook_attacks};
@@ -266,28 +536,119 @@ impl Board {
         | (rook_attacks(sq, occ) & rooks)
     }
 
+    /// Rooks and queens of either color attacking `sq` once `blockers` are lifted from `occ`.
+    /// Lifted pieces are never returned, so pieces hiding behind them show up instead.
+    #[inline]
+    pub fn xray_rook_attackers(&self, sq: Square, occ: Bitboard, blockers: Bitboard) -> Bitboard {
+        let occ = occ & !blockers;
+        let rooks = self.pieces[Piece::Rook.index()] | self.pieces[Piece::Queen.index()];
+        crate::attacks::rook_attacks(sq, occ) & rooks & occ
+    }
+
+    /// Bishops and queens of either color attacking `sq` once `blockers` are lifted from `occ`.
+    /// Lifted pieces are never returned, so pieces hiding behind them show up instead.
+    #[inline]
+    pub fn xray_bishop_attackers(&self, sq: Square, occ: Bitboard, blockers: Bitboard) -> Bitboard {
+        let occ = occ & !blockers;
+        let bishops = self.pieces[Piece::Bishop.index()] | self.pieces[Piece::Queen.index()];
+        crate::attacks::bishop_attacks(sq, occ) & bishops & occ
+    }
+
+    /// Check if `sq` is attacked by any piece of color `by`.
+    /// Tests the cheap leapers first and returns as soon as one hits.
+    #[inline]
+    pub fn is_attacked(&self, sq: Square, by: Color) -> bool {
+        use crate::attacks::{pawn_attacks, knight_attacks, king_attacks, bishop_attacks, rook_attacks};
+
+        let them = self.color_bb(by);
+        let occ = self.occupied();
+        let queens = self.pieces[Piece::Queen.index()];
+
+        (pawn_attacks(!by, sq) & self.pieces[Piece::Pawn.index()] & them).any()
+            || (knight_attacks(sq) & self.pieces[Piece::Knight.index()] & them).any()
+            || (king_attacks(sq) & self.pieces[Piece::King.index()] & them).any()
+            || (bishop_attacks(sq, occ) & (self.pieces[Piece::Bishop.index()] | queens) & them).any()
+            || (rook_attacks(sq, occ) & (self.pieces[Piece::Rook.index()] | queens) & them).any()
+    }
+
+    /// Get every square attacked by `color` with the current occupancy.
+    /// Pawns are shifted set-wise; other pieces use their attack tables.
+    pub fn attacks_by(&self, color: Color) -> Bitboard {
+        use crate::attacks::{pawn_attacks_bb, knight_attacks, king_attacks, bishop_attacks, rook_attacks};
+
+        let occ = self.occupied();
+        let queens = self.piece_color_bb(Piece::Queen, color);
+        let mut attacks = pawn_attacks_bb(color, self.piece_color_bb(Piece::Pawn, color));
+
+        for sq in self.piece_color_bb(Piece::Knight, color) {
+            attacks |= knight_attacks(sq);
+        }
+        for sq in self.piece_color_bb(Piece::Bishop, color) | queens {
+            attacks |= bishop_attacks(sq, occ);
+        }
+        for sq in self.piece_color_bb(Piece::Rook, color) | queens {
+            attacks |= rook_attacks(sq, occ);
+        }
+        for sq in self.piece_color_bb(Piece::King, color) {
+            attacks |= king_attacks(sq);
+        }
+        attacks
+    }
+
     /// Compute checkers for the side to move.
     pub fn compute_checkers(&self) -> Bitboard {
         let king_sq = self.king_square(self.turn);
         self.attackers_to(king_sq, self.occupied()) & self.them()
     }
 
-    /// Update cached checkers.
+    /// Update cached checkers and pinned pieces.
     pub fn update_checkers(&mut self) {
         self.checkers = self.compute_checkers();
+        self.pinned = self.compute_pinned();
     }
 }
 
+/// Index of a castling right, matching its bit in `CastleRights`.
+#[inline(always)]
+const fn castle_index(color: Color, kingside: bool) -> usize {
+    color.index() * 2 + !kingside as usize
+}
+
 impl Default for Board {
     fn default() -> Board {
         Board::startpos()
     }
 }
 
+/// Boards are equal when they hold the same position in the FIDE sense:
+/// same pieces, side to move, castling rights (and their rooks) and en
+/// passant possibilities. The en passant square only counts when a pawn of
+/// the side to move attacks it, so a double push nobody can capture does
+/// not make the position different. Move counters and cached state are not
+/// compared, so a position reached again later in the game compares equal.
+/// Equal boards always have equal [`Board::hash`] values.
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        self.pieces == other.pieces
+            && self.colors == other.colors
+            && self.turn == other.turn
+            && self.castling == other.castling
+            && (0..4).all(|i| {
+                self.castling.bits() & (1 << i) == 0
+                    || self.castle_rooks[i] == other.castle_rooks[i]
+            })
+            && self.capturable_ep_square() == other.capturable_ep_square()
+    }
+}
+
+impl Eq for Board {}
+
 impl core::fmt::Debug for Board {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         writeln!(f, "Board {{")?;
-        writeln!(f, "  FEN: {}", self.to_fen())?;
+        f.write_str("  FEN: ")?;
+        self.write_fen(f)?;
+        writeln!(f)?;
         writeln!(f, "  Turn: {:?}", self.turn)?;
         writeln!(f, "  Castling: {}", self.castling)?;
         writeln!(f, "  EP: {:?}", self.ep_square)?;
@@ -295,3 +656,332 @@ impl core::fmt::Debug for Board {
         write!(f, "}}")
     }
 }
+
+/// Step-by-step construction of a custom position.
+///
+/// ```
+/// use movegen::{BoardBuilder, Color, Piece, Square};
+///
+/// let board = BoardBuilder::new()
+///     .put(Square::E1, Piece::King, Color::White)
+///     .put(Square::E8, Piece::King, Color::Black)
+///     .put(Square::A7, Piece::Pawn, Color::White)
+///     .build()
+///     .unwrap();
+/// assert_eq!(board.piece_at(Square::A7), Some((Piece::Pawn, Color::White)));
+/// assert_eq!(board.turn(), Color::White);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct BoardBuilder {
+    board: Board,
+}
+
+impl BoardBuilder {
+    /// Start from an empty board with white to move.
+    pub const fn new() -> BoardBuilder {
+        BoardBuilder { board: Board::empty() }
+    }
+
+    /// Place a piece, replacing whatever was on the square.
+    pub fn put(mut self, sq: Square, piece: Piece, color: Color) -> BoardBuilder {
+        self = self.remove(sq);
+        self.board.add_piece_fast(sq, piece, color);
+        self
+    }
+
+    /// Clear a square.
+    pub fn remove(mut self, sq: Square) -> BoardBuilder {
+        if let Some((piece, color)) = self.board.piece_at(sq) {
+            self.board.remove_piece_fast(sq, piece, color);
+        }
+        self
+    }
+
+    /// Set the side to move.
+    pub fn side_to_move(mut self, color: Color) -> BoardBuilder {
+        self.board.turn = color;
+        self
+    }
+
+    /// Set the castling rights. Rooks are expected on their standard squares.
+    pub fn castling(mut self, rights: CastleRights) -> BoardBuilder {
+        self.board.castling = rights;
+        self
+    }
+
+    /// Set the en passant target square.
+    pub fn en_passant(mut self, ep: Option<Square>) -> BoardBuilder {
+        self.board.ep_square = ep;
+        self
+    }
+
+    /// Validate the position and finish it, computing the hash and checkers.
+    ///
+    /// Uses the same checks as [`Board::from_fen_strict`].
+    pub fn build(self) -> Result<Board, FenError> {
+        let mut board = self.board;
+        board.hash = board.recompute_hash();
+        board.pawn_hash = board.recompute_pawn_hash();
+        board.validate()?;
+        board.update_checkers();
+        Ok(board)
+    }
+}
+
+impl Default for BoardBuilder {
+    fn default() -> BoardBuilder {
+        BoardBuilder::new()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_each_piece_startpos() {
+        let board = Board::startpos();
+        let mut count = 0;
+        board.for_each_piece(|sq, piece, color| {
+            assert_eq!(board.piece_at(sq), Some((piece, color)));
+            count += 1;
+        });
+        assert_eq!(count, 32);
+    }
+
+    #[test]
+    fn test_position_equality() {
+        let start = Board::startpos();
+        let mut board = start;
+        for uci in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+            board = board.make_move_new(board.parse_uci(uci).unwrap());
+        }
+        assert_eq!(board.halfmove_clock(), 4);
+        assert_eq!(board, start);
+
+        // Same pieces, but castling rights or en passant differ
+        let no_castle = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w Kkq - 0 1").unwrap();
+        assert_ne!(no_castle, start);
+        let ep = Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+        let no_ep = Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+        assert_ne!(ep, no_ep);
+
+        // An en passant square no pawn can use does not change the position
+        let ep = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+        let no_ep = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+        assert_eq!(ep, no_ep);
+        assert_eq!(ep.hash(), no_ep.hash());
+        let pushed = start.make_move_new(start.parse_uci("e2e4").unwrap());
+        assert_eq!(pushed, no_ep);
+        assert_eq!(pushed.hash(), no_ep.hash());
+        assert_eq!(pushed.hash(), pushed.recompute_hash());
+
+        // A capturable en passant square still changes the hash
+        let ep = Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+        let no_ep = Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+        assert_ne!(ep.hash(), no_ep.hash());
+    }
+
+    #[test]
+    fn test_mirror() {
+        let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let mirrored = board.mirror();
+        assert_eq!(mirrored.to_fen(), "r3k2r/pppbbppp/2n2q1P/1P2p3/3pn3/BN2PNP1/P1PPQPB1/R3K2R b KQkq - 0 1");
+        assert_eq!(mirrored.mirror().to_fen(), board.to_fen());
+
+        for fen in [
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "1rqbkrbn/1ppppp1p/1n6/p1N3p1/8/2P4P/PP1PPPP1/1RQBKRBN w FBfb - 0 9",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            let mirrored = board.mirror();
+            assert_eq!(mirrored.hash(), mirrored.recompute_hash());
+            assert_eq!(mirrored.checkers(), mirrored.compute_checkers());
+            assert_eq!(mirrored.pinned(), mirrored.compute_pinned());
+            for depth in 1..=3 {
+                assert_eq!(crate::testing::perft(&board, depth), crate::testing::perft(&mirrored, depth), "{}", fen);
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_square() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        board.set_square(Square::E4, Some((Piece::Rook, Color::Black)));
+        assert_eq!(board.checkers(), Bitboard::from_square(Square::E4));
+        board.set_square(Square::E4, Some((Piece::Knight, Color::Black)));
+        assert!(board.checkers().is_empty());
+        assert_eq!(board.hash(), Board::from_fen("4k3/8/8/8/4n3/8/8/4K3 w - - 0 1").unwrap().hash());
+
+        board.set_square(Square::E4, None);
+        board.set_square(Square::E1, None);
+        assert_eq!(board.hash(), board.recompute_hash());
+        assert_eq!(board.occupied().count(), 1);
+
+        // Adding or removing a pawn next to the en passant square keeps the hash in sync
+        let mut board = Board::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").unwrap();
+        board.set_square(Square::D4, Some((Piece::Pawn, Color::Black)));
+        assert_eq!(board.hash(), board.recompute_hash());
+        board.set_square(Square::D4, None);
+        assert_eq!(board.hash(), board.recompute_hash());
+    }
+
+    #[test]
+    fn test_board_builder() {
+        let board = BoardBuilder::new()
+            .put(Square::E1, Piece::King, Color::White)
+            .put(Square::H1, Piece::Rook, Color::White)
+            .put(Square::E8, Piece::King, Color::Black)
+            .put(Square::D4, Piece::Pawn, Color::Black)
+            .put(Square::E4, Piece::Knight, Color::White)
+            .put(Square::E4, Piece::Pawn, Color::White)
+            .side_to_move(Color::Black)
+            .castling(CastleRights::WHITE_KINGSIDE)
+            .en_passant(Some(Square::E3))
+            .build()
+            .unwrap();
+        let fen = "4k3/8/8/8/3pP3/8/8/4K2R b K e3 0 1";
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(board.hash(), Board::from_fen(fen).unwrap().hash());
+
+        let missing_king = BoardBuilder::new().put(Square::E1, Piece::King, Color::White);
+        assert_eq!(missing_king.build().unwrap_err(), FenError::BadKingCount);
+        let bad_castle = BoardBuilder::new()
+            .put(Square::E1, Piece::King, Color::White)
+            .put(Square::E8, Piece::King, Color::Black)
+            .castling(CastleRights::WHITE_QUEENSIDE);
+        assert_eq!(bad_castle.build().unwrap_err(), FenError::BadCastling);
+    }
+
+    #[test]
+    fn test_xray_attackers() {
+        // Doubled rooks on the d-file, queen behind a bishop on the long diagonal
+        let board = Board::from_fen("4k3/8/8/3p4/8/2B5/1Q1R4/3RK3 w - - 0 1").unwrap();
+        let occ = board.occupied();
+        let bb = |sq| Bitboard::from_square(sq);
+
+        assert_eq!(board.xray_rook_attackers(Square::D5, occ, Bitboard::EMPTY), bb(Square::D2));
+        assert_eq!(board.xray_rook_attackers(Square::D5, occ, bb(Square::D2)), bb(Square::D1));
+        assert_eq!(board.xray_bishop_attackers(Square::D4, occ, Bitboard::EMPTY), bb(Square::C3));
+        assert_eq!(board.xray_bishop_attackers(Square::D4, occ, bb(Square::C3)), bb(Square::B2));
+    }
+
+    #[test]
+    fn test_material_count() {
+        let board = Board::startpos();
+        assert_eq!(board.piece_count(Piece::Pawn, Color::White), 8);
+        assert_eq!(board.piece_count(Piece::King, Color::Black), 1);
+        assert_eq!(board.material_count(Color::White), 8 * 100 + 4 * 300 + 2 * 500 + 900);
+
+        let board = Board::from_fen("4k3/8/8/3r4/4N3/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(board.material_count(Color::White), 300);
+        assert_eq!(board.material_count(Color::Black), 500);
+    }
+
+    #[test]
+    fn test_game_phase() {
+        assert_eq!(Board::startpos().game_phase(), 24);
+        let board = Board::from_fen("4k3/8/8/3r4/4N3/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(board.game_phase(), 3);
+        let board = Board::from_fen("qqqqk3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(board.game_phase(), 16);
+        // Eight queens would be 32 before clamping
+        let board = Board::from_fen("qqqqk3/qqqq4/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(board.game_phase(), 24);
+    }
+
+    #[test]
+    fn test_piece_iter() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        let pieces: Vec<_> = board.piece_iter().collect();
+        assert_eq!(pieces.len(), board.occupied().count() as usize);
+        assert_eq!(pieces[0], (Square::A1, Piece::Rook, Color::White));
+        for (sq, piece, color) in pieces {
+            assert_eq!(board.piece_at(sq), Some((piece, color)));
+        }
+    }
+
+    #[test]
+    fn test_move_piece_kind() {
+        use crate::movegen::{Move, MoveFlag};
+        let board = Board::startpos();
+        let mv = Move::new(Square::G1, Square::F3, MoveFlag::Quiet);
+        assert_eq!(board.move_piece_kind(mv), Piece::Knight);
+    }
+
+    #[test]
+    fn test_mailbox_roundtrip() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        let squares = board.to_mailbox();
+        assert_eq!(squares[Square::E1.index() as usize], Some((Piece::King, Color::White)));
+        assert_eq!(squares[Square::E4.index() as usize], Some((Piece::Pawn, Color::White)));
+        assert_eq!(squares[Square::D4.index() as usize], None);
+
+        let rebuilt = Board::from_mailbox(&squares, board.turn(), board.castling(), board.ep_square()).unwrap();
+        assert_eq!(rebuilt, board);
+        assert_eq!(rebuilt.hash(), board.hash());
+        assert_eq!(rebuilt.pawn_hash(), board.pawn_hash());
+
+        // Missing kings are rejected
+        assert!(Board::from_mailbox(&[None; 64], Color::White, CastleRights::NONE, None).is_err());
+    }
+
+    #[test]
+    fn test_captured_piece() {
+        use crate::movegen::{Move, MoveFlag};
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        for mv in board.generate_moves().iter() {
+            assert_eq!(board.is_capture(mv), mv.is_capture(), "{mv}");
+            assert_eq!(board.is_quiet(mv), !mv.is_capture(), "{mv}");
+        }
+        let mv = Move::new(Square::E5, Square::F7, MoveFlag::Capture);
+        assert_eq!(board.captured_piece(mv), Some(Piece::Pawn));
+        let mv = Move::new(Square::D5, Square::E6, MoveFlag::Capture);
+        assert_eq!(board.captured_piece(mv), Some(Piece::Pawn));
+        assert_eq!(board.captured_piece(Move::new(Square::F3, Square::F6, MoveFlag::Capture)), Some(Piece::Knight));
+
+        // En passant from a flagless UCI parse still reports the pawn
+        let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let mv: Move = "e5d6".parse().unwrap();
+        assert!(!mv.is_en_passant());
+        assert_eq!(board.captured_piece(mv), Some(Piece::Pawn));
+        assert!(board.is_capture(board.parse_uci("e5d6").unwrap()));
+        assert!(board.is_quiet("e5e6".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_attacks_by_and_is_attacked() {
+        let board = Board::startpos();
+        // All of ranks 2 and 3, plus b1-g1
+        let white = board.attacks_by(Color::White);
+        assert!(white.contains(Square::A3) && white.contains(Square::H3));
+        assert!(!white.contains(Square::E4));
+        assert!(!white.contains(Square::A1));
+        assert_eq!(white.count(), 22);
+
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        for color in Color::ALL {
+            let attacks = board.attacks_by(color);
+            for index in 0..64 {
+                let sq = Square::from_index(index).unwrap();
+                let attacked = (board.attackers_to(sq, board.occupied()) & board.color_bb(color)).any();
+                assert_eq!(attacks.contains(sq), attacked, "{:?} {:?}", color, sq);
+                assert_eq!(board.is_attacked(sq, color), attacked, "{:?} {:?}", color, sq);
+            }
+        }
+    }
+
+    #[test]
+    fn test_insufficient_material() {
+        let drawn = |fen| Board::from_fen(fen).unwrap().has_insufficient_material();
+        assert!(!drawn(crate::testing::STARTPOS));
+        assert!(drawn("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+        assert!(drawn("4k3/8/8/8/8/8/8/4KB2 w - - 0 1"));
+        assert!(drawn("5bk1/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+        assert!(!drawn("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+        assert!(!drawn("4k3/8/8/8/8/8/8/3BKB2 w - - 0 1"));
+        assert!(!drawn("4k3/8/8/8/8/8/8/4KR2 w - - 0 1"));
+    }
+}
@@ -0,0 +1,73 @@
+//! Input feature encodings for NNUE evaluation.
+
+use super::Board;
+use crate::types::{Color, Piece, Square};
+
+/// Width of the HalfKP input layer: 64 king squares times 641 piece-square
+/// slots (10 non-king piece kinds on 64 squares, plus the unused slot 0).
+pub const HALFKP_FEATURES: usize = 64 * 641;
+
+/// HalfKP feature index of `piece` of `color` on `sq`, seen by `perspective`
+/// whose king stands on `king_sq`.
+///
+/// Black's view is rotated 180 degrees, as in Stockfish-style networks.
+/// `piece` must not be a king.
+#[inline]
+pub const fn halfkp_index(perspective: Color, king_sq: Square, piece: Piece, color: Color, sq: Square) -> u16 {
+    debug_assert!(!matches!(piece, Piece::King));
+    let orient = if matches!(perspective, Color::White) { 0 } else { 63 };
+    let theirs = (color as u16 != perspective as u16) as u16;
+    let kind = 2 * piece.index() as u16 + theirs;
+    (king_sq.index() ^ orient) as u16 * 641 + 1 + kind * 64 + (sq.index() ^ orient) as u16
+}
+
+impl Board {
+    /// Append the active HalfKP feature indices for `perspective` to `out`,
+    /// one per non-king piece on the board. See [`halfkp_index`].
+    #[cfg(feature = "std")]
+    pub fn halfkp_features(&self, perspective: Color, out: &mut Vec<u16>) {
+        let king_sq = self.king_square(perspective);
+        let non_kings = self.occupied() & !self.piece_bb(Piece::King);
+        out.reserve(non_kings.count() as usize);
+        for sq in non_kings {
+            let (piece, color) = unsafe { self.piece_at(sq).unwrap_unchecked() };
+            out.push(halfkp_index(perspective, king_sq, piece, color, sq));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_halfkp_index_range() {
+        assert_eq!(halfkp_index(Color::White, Square::A1, Piece::Pawn, Color::White, Square::A1), 1);
+        assert_eq!(
+            halfkp_index(Color::White, Square::H8, Piece::Queen, Color::Black, Square::H8) as usize,
+            HALFKP_FEATURES - 1
+        );
+        // Black sees the board rotated, with its own pieces as "ours"
+        assert_eq!(
+            halfkp_index(Color::Black, Square::E8, Piece::Knight, Color::Black, Square::G8),
+            halfkp_index(Color::White, Square::D1, Piece::Knight, Color::White, Square::B1)
+        );
+    }
+
+    #[test]
+    fn test_halfkp_features_symmetric() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        let squares = board.to_mailbox();
+        let rotated = core::array::from_fn(|i| squares[i ^ 63].map(|(piece, color)| (piece, !color)));
+        let rotated = Board::from_mailbox(&rotated, Color::Black, crate::types::CastleRights::NONE, None).unwrap();
+
+        let (mut white, mut black) = (Vec::new(), Vec::new());
+        board.halfkp_features(Color::White, &mut white);
+        rotated.halfkp_features(Color::Black, &mut black);
+        assert_eq!(white.len(), 30);
+        assert!(white.iter().all(|&f| (f as usize) < HALFKP_FEATURES));
+        white.sort_unstable();
+        black.sort_unstable();
+        assert_eq!(white, black);
+    }
+}
@@ -0,0 +1,56 @@
+//! Pawn structure queries.
+
+use super::Board;
+use crate::bitboard::Bitboard;
+use crate::types::{Color, Piece};
+
+impl Board {
+    /// Pawns of `color` with no enemy pawn ahead of them on their own or an adjacent file.
+    pub fn passed_pawns(&self, color: Color) -> Bitboard {
+        let ours = self.piece_color_bb(Piece::Pawn, color);
+        let theirs = self.piece_color_bb(Piece::Pawn, !color);
+        // Squares in front of or beside the enemy pawns, seen from their side
+        let span = match color {
+            Color::White => theirs.south().fill_south(),
+            Color::Black => theirs.north().fill_north(),
+        };
+        ours & !(span | span.east() | span.west())
+    }
+
+    /// Pawns of `color` with no friendly pawn on an adjacent file.
+    pub fn isolated_pawns(&self, color: Color) -> Bitboard {
+        let pawns = self.piece_color_bb(Piece::Pawn, color);
+        pawns & !pawns.adjacent_files()
+    }
+
+    /// Pawns of `color` that share their file with another friendly pawn.
+    pub fn doubled_pawns(&self, color: Color) -> Bitboard {
+        let pawns = self.piece_color_bb(Piece::Pawn, color);
+        pawns & (pawns.north().fill_north() | pawns.south().fill_south())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Square;
+
+    fn squares(list: &[Square]) -> Bitboard {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_pawn_structure() {
+        // White: a2 (isolated, passed), c3 c4 (doubled), d4; black: d5 e6 h7 (isolated, passed)
+        let board = Board::from_fen("4k3/7p/4p3/3p4/2PP4/2P5/P7/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.passed_pawns(Color::White), squares(&[Square::A2]));
+        assert_eq!(board.passed_pawns(Color::Black), squares(&[Square::H7]));
+
+        assert_eq!(board.isolated_pawns(Color::White), squares(&[Square::A2]));
+        assert_eq!(board.isolated_pawns(Color::Black), squares(&[Square::H7]));
+
+        assert_eq!(board.doubled_pawns(Color::White), squares(&[Square::C3, Square::C4]));
+        assert!(board.doubled_pawns(Color::Black).is_empty());
+    }
+}
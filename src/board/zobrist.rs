@@ -1,5 +1,6 @@
 //! Zobrist hashing for board positions.
 
+use super::Board;
 use crate::types::{Square, Piece, Color, File, CastleRights};
 
 /// Zobrist hash keys.
@@ -15,15 +16,19 @@ pub struct Zobrist {
 }
 
 impl Zobrist {
-    /// Generate Zobrist keys using a simple PRNG.
-    const fn new() -> Zobrist {
+    /// Seed of the keys in [`ZOBRIST`]. Changing it changes every hash,
+    /// which breaks compatibility with stored opening books and tables.
+    pub const DEFAULT_SEED: u64 = 0x3243F6A8885A308D; // PI digits
+
+    /// Generate Zobrist keys from `seed` using a simple xorshift PRNG.
+    /// The same seed always gives the same keys; a zero seed uses [`Zobrist::DEFAULT_SEED`].
+    pub const fn with_seed(seed: u64) -> Zobrist {
         let mut piece_squares = [[[0u64; 64]; 2]; 6];
-        let side;
         let mut castling = [0u64; 16];
         let mut ep_file = [0u64; 8];
         
         // Simple xorshift PRNG
-        let mut state = 0x3243F6A8885A308Du64; // PI digits
+        let mut state = if seed == 0 { Self::DEFAULT_SEED } else { seed };
         
         // Helper to get next random
         macro_rules! next_rand {
@@ -51,7 +56,7 @@ impl Zobrist {
         }
         
         // Side to move
-        side = next_rand!(state);
+        let side = next_rand!(state);
         
         // Castling keys
         let mut i = 0;
@@ -81,6 +86,15 @@ impl Zobrist {
         self.piece_squares[piece as usize][color as usize][sq.index() as usize]
     }
 
+    /// Get the piece-square key if it belongs in the pawn hash (pawns and kings), else 0.
+    #[inline(always)]
+    pub const fn pawn_king_square(&self, piece: Piece, color: Color, sq: Square) -> u64 {
+        match piece {
+            Piece::Pawn | Piece::King => self.piece_square(piece, color, sq),
+            _ => 0,
+        }
+    }
+
     /// Get side-to-move key.
     #[inline(always)]
     pub const fn side(&self) -> u64 {
@@ -101,9 +115,68 @@ impl Zobrist {
 }
 
 /// Global Zobrist keys instance.
-pub static ZOBRIST: Zobrist = Zobrist::new();
+pub static ZOBRIST: Zobrist = Zobrist::with_seed(Zobrist::DEFAULT_SEED);
+
+impl Board {
+    /// Compute the Zobrist hash from scratch.
+    /// Should always equal the incrementally maintained [`Board::hash`].
+    pub fn recompute_hash(&self) -> u64 {
+        self.hash_with(&ZOBRIST)
+    }
+
+    /// Compute the hash of this position under a custom key set,
+    /// for example to match another engine's keys.
+    /// Only [`ZOBRIST`] hashes are maintained incrementally.
+    pub fn hash_with(&self, keys: &Zobrist) -> u64 {
+        let mut hash = 0u64;
+        for (sq, piece, color) in self.piece_iter() {
+            hash ^= keys.piece_square(piece, color, sq);
+        }
+        if self.turn() == Color::Black {
+            hash ^= keys.side();
+        }
+        hash ^= keys.castling(self.castling());
+        if let Some(ep) = self.capturable_ep_square() {
+            hash ^= keys.ep_file(ep.file());
+        }
+        hash
+    }
+
+    /// Compute the pawn hash from scratch.
+    /// Should always equal the incrementally maintained [`Board::pawn_hash`].
+    pub fn recompute_pawn_hash(&self) -> u64 {
+        let mut hash = 0u64;
+        for sq in self.piece_bb(Piece::Pawn) | self.piece_bb(Piece::King) {
+            let (piece, color) = unsafe { self.piece_at(sq).unwrap_unchecked() };
+            hash ^= ZOBRIST.piece_square(piece, color, sq);
+        }
+        hash
+    }
+
+    /// Key identifying the material on the board: the count of every
+    /// non-king piece type per color, independent of where pieces stand.
+    pub fn material_key(&self) -> u64 {
+        let mut key = 0u64;
+        for color in Color::ALL {
+            for piece in &Piece::ALL[..5] {
+                let count = self.piece_count(*piece, color).min(63) as u8;
+                let slot = unsafe { Square::from_index_unchecked(count) };
+                key ^= ZOBRIST.piece_square(*piece, color, slot);
+            }
+        }
+        key
+    }
+
+    /// Check that the cached hash and pawn hash match
+    /// [`Board::recompute_hash`] and [`Board::recompute_pawn_hash`].
+    /// Handy as a `debug_assert!` after make/unmake.
+    #[inline]
+    pub fn hash_is_valid(&self) -> bool {
+        self.hash == self.recompute_hash() && self.pawn_hash == self.recompute_pawn_hash()
+    }
+}
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -119,6 +192,64 @@ mod tests {
         assert_ne!(k2, k3);
     }
 
+    #[test]
+    fn test_recompute_hash() {
+        let board = Board::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+        ).unwrap();
+        assert_eq!(board.recompute_hash(), board.hash());
+    }
+
+    #[test]
+    fn test_hash_is_valid() {
+        let mut board = Board::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+        assert!(board.hash_is_valid());
+        assert!(board.make_null_move().hash_is_valid());
+        for mv in board.generate_moves().iter() {
+            assert!(board.make_move_new(mv).hash_is_valid());
+        }
+        board.set_square(Square::A1, None);
+        assert!(board.hash_is_valid());
+    }
+
+    #[test]
+    fn test_pawn_hash_and_material_key() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        assert_eq!(board.pawn_hash(), board.recompute_pawn_hash());
+
+        // Piece moves keep the pawn hash, pawn and king moves change it
+        for mv in board.generate_moves().iter() {
+            let after = board.make_move_new(mv);
+            assert!(after.hash_is_valid());
+            let (piece, _) = board.piece_at(mv.from()).unwrap();
+            let captures_pawn = board.piece_at(mv.to()).is_some_and(|(p, _)| p == Piece::Pawn);
+            if matches!(piece, Piece::Pawn | Piece::King) || captures_pawn {
+                assert_ne!(after.pawn_hash(), board.pawn_hash(), "{}", mv);
+            } else {
+                assert_eq!(after.pawn_hash(), board.pawn_hash(), "{}", mv);
+            }
+            assert_eq!(after.material_key() == board.material_key(), !mv.is_capture() && !mv.is_promotion(), "{}", mv);
+        }
+
+        // Same material in a different setup, different material
+        let a = Board::from_fen("4k3/8/8/8/8/8/8/RN2K3 w - - 0 1").unwrap();
+        let b = Board::from_fen("4k3/8/8/3N4/8/8/8/4K2R b - - 0 1").unwrap();
+        let c = Board::from_fen("4k3/8/8/8/8/8/8/RB2K3 w - - 0 1").unwrap();
+        assert_eq!(a.material_key(), b.material_key());
+        assert_ne!(a.material_key(), c.material_key());
+    }
+
+    #[test]
+    fn test_seeded_keys() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        assert_eq!(board.hash_with(&Zobrist::with_seed(Zobrist::DEFAULT_SEED)), board.hash());
+
+        let custom = Zobrist::with_seed(42);
+        assert_eq!(board.hash_with(&custom), board.hash_with(&Zobrist::with_seed(42)));
+        assert_ne!(board.hash_with(&custom), board.hash());
+        assert_ne!(custom.side(), 0);
+    }
+
     #[test]
     fn test_zobrist_side() {
         assert_ne!(ZOBRIST.side(), 0);
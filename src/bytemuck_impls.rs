@@ -0,0 +1,81 @@
+//! bytemuck support (only available with the bytemuck feature).
+//!
+//! All three types are `#[repr(transparent)]` over an unsigned integer, so
+//! they have no padding and can always be viewed as bytes. Only `Bitboard`
+//! accepts every bit pattern, so it alone is `Pod`. `Move` (flags 6 and 7
+//! are unused) and `Square` (must be below 64) are read back through
+//! `CheckedBitPattern`, which validates each value.
+
+use bytemuck::{CheckedBitPattern, NoUninit, Pod, Zeroable};
+
+use crate::bitboard::Bitboard;
+use crate::movegen::Move;
+use crate::types::Square;
+
+// SAFETY: transparent over u64; zero is the empty board and every
+// pattern is a valid set of squares.
+unsafe impl Zeroable for Bitboard {}
+unsafe impl Pod for Bitboard {}
+
+// SAFETY: transparent over u16 with no padding; zero is a1a1 quiet,
+// which is a valid (null) move.
+unsafe impl Zeroable for Move {}
+unsafe impl NoUninit for Move {}
+
+// SAFETY: `is_valid_bit_pattern` rejects the two unused flag values,
+// which would otherwise be transmuted into an invalid `MoveFlag`.
+unsafe impl CheckedBitPattern for Move {
+    type Bits = u16;
+
+    #[inline]
+    fn is_valid_bit_pattern(bits: &u16) -> bool {
+        !matches!(bits >> 12, 6 | 7)
+    }
+}
+
+// SAFETY: transparent over u8 with no padding; zero is A1.
+unsafe impl Zeroable for Square {}
+unsafe impl NoUninit for Square {}
+
+// SAFETY: squares index 64-entry tables unchecked, so only 0..64 is valid.
+unsafe impl CheckedBitPattern for Square {
+    type Bits = u8;
+
+    #[inline]
+    fn is_valid_bit_pattern(bits: &u8) -> bool {
+        *bits < 64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movegen::MoveFlag;
+
+    #[test]
+    fn test_bytemuck_roundtrip() {
+        let board = crate::Board::startpos();
+        let moves = board.generate_moves();
+        let bytes: &[u8] = bytemuck::cast_slice(moves.as_slice());
+        assert_eq!(bytes.len(), moves.len() * 2);
+        let back: &[Move] = bytemuck::checked::cast_slice(bytes);
+        assert_eq!(back, moves.as_slice());
+
+        let bbs = [Bitboard::RANK_1, Bitboard(u64::MAX)];
+        let words: &[u64] = bytemuck::cast_slice(&bbs);
+        assert_eq!(words, &[Bitboard::RANK_1.0, u64::MAX]);
+
+        let squares = [Square::E4, Square::H8];
+        let raw: &[u8] = bytemuck::cast_slice(&squares);
+        assert_eq!(bytemuck::checked::cast_slice::<u8, Square>(raw), &squares);
+    }
+
+    #[test]
+    fn test_bytemuck_rejects_invalid_patterns() {
+        assert!(bytemuck::checked::try_cast::<u8, Square>(64).is_err());
+        let bad_flag = (6u16 << 12) | 1;
+        assert!(bytemuck::checked::try_cast::<u16, Move>(bad_flag).is_err());
+        let ok = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
+        assert_eq!(bytemuck::checked::try_cast::<u16, Move>(ok.bits()), Ok(ok));
+    }
+}
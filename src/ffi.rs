@@ -0,0 +1,147 @@
+//! C ABI for embedding the move generator in other languages.
+//!
+//! Boards are opaque heap pointers and moves are their raw 16-bit encoding
+//! (see [`Move`]). Build with `--features ffi` and `--crate-type cdylib` or
+//! `staticlib`.
+
+use core::ffi::{c_char, CStr};
+use crate::board::Board;
+use crate::movegen::Move;
+
+/// Parse a NUL-terminated FEN string into a new board.
+///
+/// Returns null if `fen` is null, not UTF-8 or rejected by
+/// [`Board::from_fen_strict`]. Free the result with [`mg_board_free`].
+///
+/// # Safety
+/// `fen` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mg_board_from_fen(fen: *const c_char) -> *mut Board {
+    if fen.is_null() {
+        return core::ptr::null_mut();
+    }
+    match CStr::from_ptr(fen).to_str().ok().and_then(|s| Board::from_fen_strict(s).ok()) {
+        Some(board) => Box::into_raw(Box::new(board)),
+        None => core::ptr::null_mut(),
+    }
+}
+
+/// Free a board returned by [`mg_board_from_fen`]. Null is ignored.
+///
+/// # Safety
+/// `board` must be null or a pointer from [`mg_board_from_fen`] that has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn mg_board_free(board: *mut Board) {
+    if !board.is_null() {
+        drop(Box::from_raw(board));
+    }
+}
+
+/// Write the legal moves into `out` and return how many there are.
+///
+/// At most `cap` moves are written; 256 always suffices. A return value
+/// larger than `cap` means the buffer was too small.
+///
+/// # Safety
+/// `board` must be a live board and `out` must be valid for `cap` writes.
+#[no_mangle]
+pub unsafe extern "C" fn mg_generate_moves(board: *const Board, out: *mut u16, cap: usize) -> usize {
+    // Move is a transparent u16
+    let buf = if cap == 0 { &mut [] } else { core::slice::from_raw_parts_mut(out.cast::<Move>(), cap) };
+    (*board).generate_moves_slice(buf)
+}
+
+/// Play `mv` if it is legal. Returns `false` and leaves the board
+/// untouched otherwise, including for bit patterns that are not a valid
+/// [`Move`] encoding.
+///
+/// # Safety
+/// `board` must be a live board.
+#[no_mangle]
+pub unsafe extern "C" fn mg_make_move(board: *mut Board, mv: u16) -> bool {
+    // Flag nibbles 6 and 7 are unassigned and must never reach MoveFlag
+    if matches!(mv >> 12, 6 | 7) {
+        return false;
+    }
+    (*board).try_make_move(Move::from_bits(mv)).is_ok()
+}
+
+/// Write the FEN of `board` into `buf` as a NUL-terminated string.
+///
+/// Like `snprintf`, returns the FEN length without the terminator and
+/// truncates if `len` is too small; 128 bytes always suffice.
+///
+/// # Safety
+/// `board` must be a live board and `buf` must be valid for `len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn mg_to_fen(board: *const Board, buf: *mut c_char, len: usize) -> usize {
+    let fen = (*board).to_fen();
+    if len > 0 {
+        let n = fen.len().min(len - 1);
+        core::ptr::copy_nonoverlapping(fen.as_ptr(), buf.cast::<u8>(), n);
+        *buf.add(n) = 0;
+    }
+    fen.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::KIWIPETE;
+
+    #[test]
+    fn test_ffi_roundtrip() {
+        unsafe {
+            let board = mg_board_from_fen(c"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".as_ptr());
+            assert!(!board.is_null());
+
+            let mut moves = [0u16; 256];
+            let count = mg_generate_moves(board, moves.as_mut_ptr(), moves.len());
+            assert_eq!(count, 20);
+            assert_eq!(mg_generate_moves(board, moves.as_mut_ptr(), 4), 20);
+
+            let e2e4 = (*board).parse_uci("e2e4").unwrap().bits();
+            assert!(moves[..count].contains(&e2e4));
+            assert!(mg_make_move(board, e2e4));
+            assert!(!mg_make_move(board, e2e4));
+
+            let mut buf = [0 as c_char; 128];
+            let len = mg_to_fen(board, buf.as_mut_ptr(), buf.len());
+            let fen = CStr::from_ptr(buf.as_ptr()).to_str().unwrap();
+            assert_eq!(fen, "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+            assert_eq!(len, fen.len());
+
+            // Truncated output is still terminated
+            assert_eq!(mg_to_fen(board, buf.as_mut_ptr(), 9), len);
+            assert_eq!(CStr::from_ptr(buf.as_ptr()).to_str().unwrap(), "rnbqkbnr");
+            mg_board_free(board);
+        }
+    }
+
+    #[test]
+    fn test_ffi_invalid_move_bits() {
+        unsafe {
+            let board = mg_board_from_fen(c"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".as_ptr());
+            let e2e4 = (*board).parse_uci("e2e4").unwrap().bits();
+            assert!(!mg_make_move(board, 0x6000 | e2e4));
+            assert!(!mg_make_move(board, (e2e4 & 0x0FFF) | 0x6000));
+            assert!(!mg_make_move(board, (e2e4 & 0x0FFF) | 0x7000));
+            assert_eq!((*board).to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+            mg_board_free(board);
+        }
+    }
+
+    #[test]
+    fn test_ffi_bad_fen() {
+        unsafe {
+            assert!(mg_board_from_fen(core::ptr::null()).is_null());
+            assert!(mg_board_from_fen(c"not a fen".as_ptr()).is_null());
+            assert!(mg_board_from_fen(c"8/8/8/8/8/8/8/8 w - - 0 1".as_ptr()).is_null());
+            let board = mg_board_from_fen(c"r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1".as_ptr());
+            assert_eq!((*board).to_fen(), KIWIPETE);
+            mg_board_free(board);
+            mg_board_free(core::ptr::null_mut());
+        }
+    }
+}
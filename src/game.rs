@@ -0,0 +1,142 @@
+//! Game record with position history (only available with std feature).
+
+use crate::board::{Board, UndoInfo};
+use crate::movegen::Move;
+
+/// A board plus the moves that led to it.
+///
+/// `Board` is `Copy` and knows nothing about earlier positions, so
+/// repetition detection lives here.
+#[derive(Clone)]
+pub struct Game {
+    board: Board,
+    /// Hash of each position before the corresponding move.
+    hashes: Vec<u64>,
+    /// Moves played, oldest first.
+    moves: Vec<Move>,
+    undos: Vec<UndoInfo>,
+}
+
+impl Game {
+    /// Start a game from the given position.
+    pub fn new(board: Board) -> Game {
+        Game {
+            board,
+            hashes: Vec::new(),
+            moves: Vec::new(),
+            undos: Vec::new(),
+        }
+    }
+
+    /// Get the current position.
+    #[inline(always)]
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Get the moves played so far.
+    #[inline(always)]
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Play a move. The move must be legal in the current position.
+    pub fn make_move(&mut self, mv: Move) {
+        self.hashes.push(self.board.hash());
+        self.moves.push(mv);
+        self.undos.push(self.board.make_move(mv));
+    }
+
+    /// Take back the last move. Returns it, or `None` at the start.
+    pub fn undo(&mut self) -> Option<Move> {
+        let mv = self.moves.pop()?;
+        let undo = self.undos.pop()?;
+        self.hashes.pop();
+        self.board.unmake_move(mv, undo);
+        Some(mv)
+    }
+
+    /// Count earlier occurrences of the current position.
+    ///
+    /// Only looks back to the last capture or pawn move, since nothing
+    /// before it can repeat.
+    pub fn repetitions(&self) -> usize {
+        let hash = self.board.hash();
+        let window = (self.board.halfmove_clock() as usize).min(self.hashes.len());
+        self.hashes[self.hashes.len() - window..]
+            .iter()
+            .rev()
+            .skip(1)
+            .step_by(2)
+            .filter(|&&h| h == hash)
+            .count()
+    }
+
+    /// Check if the current position has occurred three times.
+    #[inline]
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetitions() >= 2
+    }
+}
+
+impl Default for Game {
+    fn default() -> Game {
+        Game::new(Board::startpos())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movegen::MoveFlag;
+    use crate::types::Square;
+
+    fn shuffle_knights(game: &mut Game) {
+        for (from, to) in [
+            (Square::G1, Square::F3),
+            (Square::G8, Square::F6),
+            (Square::F3, Square::G1),
+            (Square::F6, Square::G8),
+        ] {
+            game.make_move(Move::new(from, to, MoveFlag::Quiet));
+        }
+    }
+
+    #[test]
+    fn test_threefold_repetition() {
+        let mut game = Game::default();
+        shuffle_knights(&mut game);
+        assert_eq!(game.repetitions(), 1);
+        assert!(!game.is_threefold_repetition());
+        shuffle_knights(&mut game);
+        assert!(game.is_threefold_repetition());
+
+        game.undo();
+        assert!(!game.is_threefold_repetition());
+    }
+
+    #[test]
+    fn test_repetition_after_uncapturable_double_push() {
+        // The position after 1.e4 recurs after 3.Ng1 and 5.Ng1, even though
+        // only the first occurrence has an en passant square set
+        let mut game = Game::default();
+        for uci in ["e2e4", "b8c6", "g1f3", "c6b8", "f3g1", "b8c6", "g1f3", "c6b8", "f3g1"] {
+            let mv = game.board().parse_uci(uci).unwrap();
+            game.make_move(mv);
+        }
+        assert_eq!(game.repetitions(), 2);
+        assert!(game.is_threefold_repetition());
+    }
+
+    #[test]
+    fn test_pawn_move_resets_window() {
+        let mut game = Game::default();
+        shuffle_knights(&mut game);
+        game.make_move(Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush));
+        assert_eq!(game.repetitions(), 0);
+
+        while game.undo().is_some() {}
+        assert_eq!(game.board().hash(), Board::startpos().hash());
+        assert!(game.moves().is_empty());
+    }
+}
@@ -6,6 +6,26 @@
 //! ## Features
 //! - `std` (default): Enable standard library features
 //! - `pext`: Enable BMI2 PEXT instructions for faster sliding attacks
+//! - `variants`: Enable `VariantMove` with crazyhouse-style piece drops
+//! - `parallel`: Enable multi-threaded `testing::perft_parallel`
+//! - `serde`: Serialize/Deserialize support (boards as FEN, moves as raw bits)
+//! - `bytemuck`: Zero-copy byte casts for `Move`, `Bitboard` and `Square`
+//! - `rand`: `Board::random_move` and `testing::random_legal_position` over
+//!   any `rand_core::RngCore`
+//! - `wasm`: `wasm::WasmBoard`, a `wasm-bindgen` wrapper exported to JS as `Board`
+//! - `ffi`: `extern "C"` functions (`mg_board_from_fen`, `mg_generate_moves`, ...)
+//! - `runtime-magics`: Build the magic attack tables on first use instead of
+//!   embedding them in the binary (about 800KB smaller)
+//! - `no-tables`: Compute slider attacks with hyperbola quintessence and no
+//!   lookup tables; slower, but takes precedence over `pext` and `runtime-magics`
+//!
+//! ## `no_std`
+//! Without `std` the crate needs no allocator. FEN parsing, move generation,
+//! make/unmake, hashing and UCI parsing all work; text output goes through
+//! [`Board::write_fen`], [`Move::write_uci`] and the `Display` impls. The
+//! `String`-returning helpers (`to_fen`, `to_uci`, `pretty_print`, SAN), the
+//! `game` and `testing` modules, the binaries and examples, and the unit
+//! tests built on them require `std`.
 //!
 //! ## Example
 //! ```
@@ -31,11 +51,29 @@ pub mod attacks;
 pub mod board;
 pub mod movegen;
 
+#[cfg(feature = "std")]
+pub mod game;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impls;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 #[cfg(feature = "std")]
 pub mod testing;
 
 // Re-export commonly used types
-pub use types::{Square, File, Rank, Color, Piece, CastleRights};
+pub use types::{Square, File, Rank, Color, Piece, CastleRights, PIECE_VALUES, PHASE_WEIGHTS};
 pub use bitboard::Bitboard;
-pub use board::Board;
-pub use movegen::{Move, MoveList, MoveFlag};
+pub use board::{Board, BoardBuilder, FenError, IllegalMove};
+pub use movegen::{Move, MoveList, MoveFlag, GameStatus, CheckType};
+
+#[cfg(feature = "std")]
+pub use game::Game;
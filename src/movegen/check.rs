@@ -0,0 +1,183 @@
+//! Check prediction without making the move.
+
+use super::{Move, MoveFlag};
+use crate::attacks::{bishop_attacks, knight_attacks, line, pawn_attacks, rook_attacks};
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::types::{Piece, Square};
+
+/// How the side to move is in check, from [`Board::check_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CheckType {
+    /// Not in check.
+    None,
+    /// In check from the piece on this square.
+    Single(Square),
+    /// In check from two pieces; only king moves are legal.
+    Double,
+}
+
+impl Board {
+    /// Classify the current check: none, single (with the checker's square) or double.
+    #[inline]
+    pub fn check_type(&self) -> CheckType {
+        let checkers = self.checkers();
+        if checkers.is_empty() {
+            CheckType::None
+        } else if checkers.more_than_one() {
+            CheckType::Double
+        } else {
+            CheckType::Single(unsafe { checkers.lsb_unchecked() })
+        }
+    }
+
+    /// Check if `mv` would put the opponent in check.
+    ///
+    /// Covers direct checks, discovered checks, the rook in castling,
+    /// both pawns leaving the board in en passant, and the promoted piece.
+    /// `mv` is assumed to be legal in this position.
+    pub fn gives_check(&self, mv: Move) -> bool {
+        let us = self.turn();
+        let king_sq = self.king_square(!us);
+        let from = mv.from();
+        let to = mv.to();
+        let flag = mv.flag();
+        let piece = flag.promotion_piece().unwrap_or_else(|| self.move_piece_kind(mv));
+
+        let from_bb = Bitboard::from_square(from);
+        let to_bb = Bitboard::from_square(to);
+        let mut occ = (self.occupied() & !from_bb) | to_bb;
+        let mut diagonal = (self.piece_bb(Piece::Bishop) | self.piece_bb(Piece::Queen)) & self.us() & !from_bb;
+        let mut orthogonal = (self.piece_bb(Piece::Rook) | self.piece_bb(Piece::Queen)) & self.us() & !from_bb;
+
+        match flag {
+            MoveFlag::EnPassant => {
+                let cap_sq = unsafe { Square::from_index_unchecked((to.index() as i8 - us.pawn_push()) as u8) };
+                occ &= !Bitboard::from_square(cap_sq);
+            }
+            MoveFlag::KingCastle | MoveFlag::QueenCastle => {
+                let kingside = flag == MoveFlag::KingCastle;
+                let rook_from = Bitboard::from_square(self.castle_rook_square(us, kingside));
+                let rook_to = Bitboard::from_square(self.castle_destinations(us, kingside).1);
+                occ = (occ & !rook_from) | to_bb | rook_to;
+                orthogonal = (orthogonal & !rook_from) | rook_to;
+            }
+            _ => {}
+        }
+
+        // The moved piece itself
+        match piece {
+            Piece::Pawn => {
+                if pawn_attacks(us, to).contains(king_sq) {
+                    return true;
+                }
+            }
+            Piece::Knight => {
+                if knight_attacks(to).contains(king_sq) {
+                    return true;
+                }
+            }
+            Piece::Bishop => diagonal |= to_bb,
+            Piece::Rook => orthogonal |= to_bb,
+            Piece::Queen => {
+                diagonal |= to_bb;
+                orthogonal |= to_bb;
+            }
+            Piece::King => {}
+        }
+
+        // Sliders, including any the move uncovered
+        (bishop_attacks(king_sq, occ) & diagonal).any() || (rook_attacks(king_sq, occ) & orthogonal).any()
+    }
+
+    /// Get the sliders of the side to move that would check the enemy king
+    /// if the piece on `from` left its square.
+    ///
+    /// These are the pin rays of the enemy king where `from` is the only
+    /// blocker, whichever side owns it. Where the piece lands is not
+    /// considered, so a move along the ray still counts here.
+    pub fn discovered_checkers_if_moved(&self, from: Square) -> Bitboard {
+        let us = self.turn();
+        let king_sq = self.king_square(!us);
+        let ray = line(king_sq, from);
+        if ray.is_empty() {
+            return Bitboard::EMPTY;
+        }
+
+        let occ = self.occupied() & !Bitboard::from_square(from);
+        let diagonal = (self.piece_bb(Piece::Bishop) | self.piece_bb(Piece::Queen)) & self.us();
+        let orthogonal = (self.piece_bb(Piece::Rook) | self.piece_bb(Piece::Queen)) & self.us();
+        let checkers = (bishop_attacks(king_sq, occ) & diagonal) | (rook_attacks(king_sq, occ) & orthogonal);
+        checkers & ray & !Bitboard::from_square(from)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing::{KIWIPETE, POSITION_3, POSITION_4, POSITION_5};
+
+    #[test]
+    fn test_gives_check_matches_make_move() {
+        let fens = [
+            KIWIPETE,
+            POSITION_3,
+            POSITION_4,
+            POSITION_5,
+            // Castling rook checks the king on f8
+            "5k2/8/8/8/8/8/8/4K2R w K - 0 1",
+            // En passant opens the fifth rank
+            "8/8/8/R2pP2k/8/8/8/K7 w - d6 0 1",
+        ];
+        for fen in fens {
+            crate::testing::perft_with(&Board::from_fen(fen).unwrap(), 2, |board, mv| {
+                let after = board.make_move_new(mv);
+                assert_eq!(board.gives_check(mv), after.in_check(), "{} in {}", mv, board.to_fen());
+            });
+        }
+    }
+
+    #[test]
+    fn test_check_type() {
+        assert_eq!(Board::startpos().check_type(), CheckType::None);
+        let board = Board::from_fen("4k3/8/8/8/8/3n4/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(board.check_type(), CheckType::Single(Square::D3));
+        // Rook on e8 and knight on d3
+        let board = Board::from_fen("k3r3/8/8/8/8/3n4/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(board.check_type(), CheckType::Double);
+    }
+
+    #[test]
+    fn test_discovered_checkers_if_moved() {
+        // Bishop on c3 behind the e5 knight
+        let board = Board::from_fen("8/8/5k2/4N3/8/2B5/8/K7 w - - 0 1").unwrap();
+        assert_eq!(board.discovered_checkers_if_moved(Square::E5), Bitboard::from_square(Square::C3));
+        assert!(board.discovered_checkers_if_moved(Square::C3).is_empty());
+        assert!(board.discovered_checkers_if_moved(Square::D4).is_empty());
+
+        // Brute force: lift each piece and look for new slider attacks on the king
+        for fen in [KIWIPETE, POSITION_3, POSITION_4, POSITION_5, "4k3/4r3/8/4B3/8/4R3/8/4K2Q b - - 0 1"] {
+            let board = Board::from_fen(fen).unwrap();
+            let king_sq = board.king_square(!board.turn());
+            let sliders = board.us() & !board.piece_bb(Piece::Pawn) & !board.piece_bb(Piece::Knight) & !board.piece_bb(Piece::King);
+            for from in board.occupied() & !Bitboard::from_square(king_sq) {
+                let occ = board.occupied() & !Bitboard::from_square(from);
+                let expected = board.attackers_to(king_sq, occ) & sliders & !Bitboard::from_square(from);
+                assert_eq!(board.discovered_checkers_if_moved(from), expected, "{fen} {from}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_gives_check_special_moves() {
+        let board = Board::from_fen("5k2/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert!(board.gives_check(Move::new(Square::E1, Square::G1, MoveFlag::KingCastle)));
+
+        let board = Board::from_fen("8/8/8/R2pP2k/8/8/8/K7 w - d6 0 1").unwrap();
+        assert!(board.gives_check(Move::new(Square::E5, Square::D6, MoveFlag::EnPassant)));
+
+        let board = Board::from_fen("7k/1P6/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        assert!(board.gives_check(Move::new(Square::B7, Square::B8, MoveFlag::PromoRook)));
+        assert!(!board.gives_check(Move::new(Square::B7, Square::B8, MoveFlag::PromoBishop)));
+    }
+}
@@ -0,0 +1,72 @@
+//! Piece-to history table for quiet move ordering.
+
+use crate::types::{Square, Piece};
+
+/// History scores indexed by [moving piece][destination square].
+///
+/// Updates use the usual gravity formula, so scores approach
+/// `±HistoryTable::MAX` asymptotically and never overflow.
+#[derive(Clone)]
+pub struct HistoryTable {
+    table: [[i16; 64]; 6],
+}
+
+impl HistoryTable {
+    /// Maximum absolute score.
+    pub const MAX: i32 = 16384;
+
+    /// Create a zeroed table.
+    #[inline]
+    pub const fn new() -> HistoryTable {
+        HistoryTable { table: [[0; 64]; 6] }
+    }
+
+    /// Get the score for a piece moving to a square.
+    #[inline(always)]
+    pub fn get(&self, piece: Piece, to: Square) -> i16 {
+        self.table[piece.index()][to.index() as usize]
+    }
+
+    /// Apply a bonus (or malus, if negative) with gravity.
+    #[inline]
+    pub fn update(&mut self, piece: Piece, to: Square, bonus: i32) {
+        let bonus = bonus.clamp(-Self::MAX, Self::MAX);
+        let entry = &mut self.table[piece.index()][to.index() as usize];
+        let current = *entry as i32;
+        *entry = (current + bonus - current * bonus.abs() / Self::MAX) as i16;
+    }
+
+    /// Reset all scores to zero.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.table = [[0; 64]; 6];
+    }
+}
+
+impl Default for HistoryTable {
+    fn default() -> HistoryTable {
+        HistoryTable::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_saturates() {
+        let mut history = HistoryTable::new();
+        for _ in 0..10_000 {
+            history.update(Piece::Knight, Square::F3, 2000);
+        }
+        let score = history.get(Piece::Knight, Square::F3) as i32;
+        assert!(score > 0 && score <= HistoryTable::MAX);
+        assert!(score > HistoryTable::MAX - 100);
+
+        for _ in 0..10_000 {
+            history.update(Piece::Knight, Square::F3, -100_000);
+        }
+        assert!(history.get(Piece::Knight, Square::F3) as i32 >= -HistoryTable::MAX);
+        assert_eq!(history.get(Piece::Bishop, Square::F3), 0);
+    }
+}
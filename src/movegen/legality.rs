@@ -1 +1,208 @@
-//! Legality and pin detection (placeholder - handled in mod.rs).
+//! Legality checks for moves from untrusted sources.
+
+use super::{Move, MoveFlag};
+use crate::attacks::{between, bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks};
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::types::{Piece, Square};
+
+impl Board {
+    /// Check if `mv` is legal in this position without trusting it.
+    ///
+    /// Meant for hash moves, killers and user input that may come from a
+    /// different position. Everything [`Board::is_pseudo_legal`] checks,
+    /// plus: our king is not left in check (including en passant
+    /// discoveries), and castling neither starts in, passes through nor
+    /// lands on an attacked square.
+    pub fn is_legal(&self, mv: Move) -> bool {
+        if !self.is_pseudo_legal(mv) {
+            return false;
+        }
+
+        let us = self.turn();
+        let from = mv.from();
+        let to = mv.to();
+        let flag = mv.flag();
+
+        match flag {
+            MoveFlag::KingCastle => return self.can_castle_kingside(us),
+            MoveFlag::QueenCastle => return self.can_castle_queenside(us),
+            _ => {}
+        }
+
+        let mut captured = if flag.is_capture() { Bitboard::from_square(to) } else { Bitboard::EMPTY };
+        if flag == MoveFlag::EnPassant {
+            captured = Bitboard::from_square(ep_victim(to, us.pawn_push()));
+        }
+        let occ = (self.occupied() ^ Bitboard::from_square(from) ^ captured) | Bitboard::from_square(to);
+        let king_sq = if from == self.king_square(us) { to } else { self.king_square(us) };
+        (self.attackers_to(king_sq, occ) & self.them() & !captured).is_empty()
+    }
+
+    /// Check if `mv` could be played here if king safety were ignored.
+    ///
+    /// Guarantees that the side to move has a piece on the from-square that
+    /// can reach the to-square with this flag: the path is clear, captures
+    /// land on an enemy piece and quiet moves on an empty square, double
+    /// pushes start on the pawn rank, en passant targets the current ep
+    /// square, promotions reach the last rank, and castling has its right,
+    /// its rook, and an empty path. It does *not* check pins, check
+    /// evasion, or attacked castling squares; use [`Board::is_legal`] for that.
+    pub fn is_pseudo_legal(&self, mv: Move) -> bool {
+        let us = self.turn();
+        let piece = match self.piece_at(mv.from()) {
+            Some((piece, color)) if color == us => piece,
+            _ => return false,
+        };
+
+        let flag = mv.flag();
+        if matches!(flag, MoveFlag::KingCastle | MoveFlag::QueenCastle) {
+            let kingside = flag == MoveFlag::KingCastle;
+            let has_right = if kingside {
+                self.castling().has_kingside(us)
+            } else {
+                self.castling().has_queenside(us)
+            };
+            return piece == Piece::King
+                && has_right
+                && mv.to() == self.castle_destinations(us, kingside).0
+                && self.castle_path_clear(kingside);
+        }
+
+        !self.us().contains(mv.to()) && self.fits_piece(mv, piece)
+    }
+
+    /// Check that the castling rook is in place and nothing else stands
+    /// on the king's or rook's path.
+    fn castle_path_clear(&self, kingside: bool) -> bool {
+        let us = self.turn();
+        let king = self.king_square(us);
+        let rook = self.castle_rook_square(us, kingside);
+        let (king_to, rook_to) = self.castle_destinations(us, kingside);
+
+        let occ = self.occupied() ^ Bitboard::from_square(king) ^ Bitboard::from_square(rook);
+        let path = between(king, king_to) | between(rook, rook_to)
+            | Bitboard::from_square(king_to) | Bitboard::from_square(rook_to);
+        self.piece_color_bb(Piece::Rook, us).contains(rook) && (occ & path).is_empty()
+    }
+
+    /// Check that a non-castling `mv` fits the piece on its from-square.
+    fn fits_piece(&self, mv: Move, piece: Piece) -> bool {
+        let us = self.turn();
+        let from = mv.from();
+        let to = mv.to();
+        let flag = mv.flag();
+        let occ = self.occupied();
+        let enemy_at_to = self.them().contains(to);
+
+        if piece != Piece::Pawn {
+            let attacks = match piece {
+                Piece::Knight => knight_attacks(from),
+                Piece::Bishop => bishop_attacks(from, occ),
+                Piece::Rook => rook_attacks(from, occ),
+                Piece::Queen => bishop_attacks(from, occ) | rook_attacks(from, occ),
+                _ => king_attacks(from),
+            };
+            return attacks.contains(to)
+                && match flag {
+                    MoveFlag::Quiet => !enemy_at_to,
+                    MoveFlag::Capture => enemy_at_to,
+                    _ => false,
+                };
+        }
+
+        let push = us.pawn_push();
+        let single = from.index() as i8 + push;
+        let to_promo_rank = to.rank() == us.promotion_rank();
+        let is_push = to.index() as i8 == single && !occ.contains(to);
+        let is_attack = pawn_attacks(us, from).contains(to);
+
+        match flag {
+            MoveFlag::Quiet => is_push && !to_promo_rank,
+            MoveFlag::Capture => is_attack && enemy_at_to && !to_promo_rank,
+            MoveFlag::DoublePawnPush => {
+                from.rank() == us.pawn_rank()
+                    && to.index() as i8 == single + push
+                    && !occ.contains(to)
+                    && !occ.contains(unsafe { Square::from_index_unchecked(single as u8) })
+            }
+            MoveFlag::EnPassant => is_attack && self.ep_square() == Some(to),
+            _ if flag.is_promotion() => {
+                to_promo_rank && if flag.is_capture() { is_attack && enemy_at_to } else { is_push }
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Square of the pawn captured en passant on `to`.
+#[inline(always)]
+fn ep_victim(to: Square, push: i8) -> Square {
+    unsafe { Square::from_index_unchecked((to.index() as i8 - push) as u8) }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing::{KIWIPETE, POSITION_3, POSITION_4, POSITION_5};
+
+    /// Every encodable move between every pair of squares.
+    fn all_moves() -> impl Iterator<Item = Move> {
+        (0..64u8).flat_map(|from| {
+            (0..64u8).flat_map(move |to| {
+                (0..16u8).filter(|flag| !matches!(flag, 6 | 7)).map(move |flag| {
+                    let from = Square::from_index(from).unwrap();
+                    let to = Square::from_index(to).unwrap();
+                    Move::new(from, to, MoveFlag::from_u8(flag))
+                })
+            })
+        })
+    }
+
+    #[test]
+    fn test_is_legal_matches_generator() {
+        let fens = [
+            crate::testing::STARTPOS,
+            KIWIPETE,
+            POSITION_3,
+            POSITION_4,
+            POSITION_5,
+            // En passant would expose the king along the rank
+            "8/8/8/KPp4r/8/8/8/7k w - c6 0 1",
+            // Chess960, king on g1 with rooks on f1 and h1
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        ];
+        for fen in fens {
+            let board = Board::from_fen(fen).unwrap();
+            let legal = board.generate_moves();
+            for mv in all_moves() {
+                assert_eq!(board.is_legal(mv), legal.contains(mv), "{} {:?} in {}", mv, mv.flag(), fen);
+                if legal.contains(mv) {
+                    assert!(board.is_pseudo_legal(mv));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_pseudo_legal_ignores_king_safety() {
+        // The e-pawn is pinned by the rook on e8
+        let board = Board::from_fen("4r1k1/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+        let push = Move::new(Square::E2, Square::E3, MoveFlag::Quiet);
+        let sidestep = Move::new(Square::E2, Square::D3, MoveFlag::Quiet);
+        assert!(board.is_pseudo_legal(push) && board.is_legal(push));
+
+        let board = Board::from_fen("6k1/8/8/8/8/8/4P3/r3K3 w - - 0 1").unwrap();
+        assert!(board.is_pseudo_legal(push) && !board.is_legal(push));
+        assert!(!board.is_pseudo_legal(sidestep));
+
+        // Castling through an attacked square is pseudo-legal only
+        let board = Board::from_fen("5rk1/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        let castle = Move::new(Square::E1, Square::G1, MoveFlag::KingCastle);
+        assert!(board.is_pseudo_legal(castle) && !board.is_legal(castle));
+
+        // A right without its rook is not enough
+        let board = Board::from_fen("6k1/8/8/8/8/8/8/4K3 w K - 0 1").unwrap();
+        assert!(!board.is_pseudo_legal(castle));
+    }
+}
@@ -6,8 +6,25 @@ mod knights;
 mod king;
 mod sliders;
 mod legality;
+mod history;
+#[cfg(feature = "std")]
+mod san;
+mod check;
+mod status;
+mod uci;
+#[cfg(feature = "variants")]
+mod variant;
+#[cfg(feature = "rand")]
+mod random;
 
-pub use moves::{Move, MoveFlag, MoveList, ScoredMove, MoveSink, MoveCounter};
+pub use moves::{Move, MoveFlag, MoveList, MoveListFull, MoveSet, ScoredMove, MoveSink, MoveCounter, ClosureSink, ParseMoveError};
+pub use history::HistoryTable;
+#[cfg(feature = "std")]
+pub use san::SanError;
+pub use status::GameStatus;
+pub use check::CheckType;
+#[cfg(feature = "variants")]
+pub use variant::VariantMove;
 
 use crate::bitboard::Bitboard;
 use crate::board::Board;
@@ -18,19 +35,47 @@ impl Board {
     /// Generate all legal moves.
     pub fn generate_moves(&self) -> MoveList {
         let mut moves = MoveList::new();
-        self.generate_moves_impl(&mut moves);
+        self.generate_moves_into(&mut moves);
         moves
     }
 
+    /// Write the legal moves into `buf` and return how many there are.
+    ///
+    /// Moves past the end of `buf` are counted but dropped, so a return value
+    /// larger than `buf.len()` means the buffer was too small.
+    pub fn generate_moves_slice(&self, buf: &mut [Move]) -> usize {
+        let mut sink = SliceSink { buf, len: 0 };
+        self.generate_moves_into(&mut sink);
+        sink.len
+    }
+
+    /// Generate all legal moves except `skip` (for singular extensions).
+    pub fn generate_moves_excluding(&self, skip: Move) -> MoveList {
+        let mut sink = ExcludingSink { inner: MoveList::new(), skip };
+        self.generate_moves_into(&mut sink);
+        sink.inner
+    }
+
+    /// Check if the side to move has at least one legal move.
+    /// Stops generating as soon as one is found.
+    pub fn has_legal_moves(&self) -> bool {
+        let mut finder = FirstMove { found: false };
+        self.generate_moves_into(&mut finder);
+        finder.found
+    }
+
     /// Generate count of all legal moves (bulk count).
     pub fn generate_moves_count(&self) -> u64 {
         let mut counter = MoveCounter::new();
-        self.generate_moves_impl(&mut counter);
+        self.generate_moves_into(&mut counter);
         counter.count
     }
 
-    /// Generic move generation implementation.
-    fn generate_moves_impl<M: MoveSink>(&self, moves: &mut M) {
+    /// Generate all legal moves into any [`MoveSink`], such as a [`ClosureSink`].
+    ///
+    /// [`generate_moves`](Board::generate_moves) and
+    /// [`generate_moves_count`](Board::generate_moves_count) are thin wrappers over this.
+    pub fn generate_moves_into<M: MoveSink>(&self, moves: &mut M) {
         if self.checkers().more_than_one() {
             // Double check: only king moves are legal
             self.generate_king_moves(moves);
@@ -45,15 +90,163 @@ impl Board {
 
     /// Generate all pseudolegal moves (no check test).
     fn generate_all_moves<M: MoveSink>(&self, moves: &mut M) {
-        let pinned = self.compute_pinned();
+        self.generate_non_king_all(moves);
+        if moves.should_stop() {
+            return;
+        }
+        self.generate_king_moves(moves);
+    }
+
+    /// Generate all legal moves except those of the king.
+    pub fn generate_non_king_moves(&self) -> MoveList {
+        let mut moves = MoveList::new();
+        if self.checkers().more_than_one() {
+            // Double check: only the king can move
+        } else if self.checkers().any() {
+            self.generate_non_king_evasions(&mut moves);
+        } else {
+            self.generate_non_king_all(&mut moves);
+        }
+        moves
+    }
+
+    /// Internal: non-king moves when not in check.
+    fn generate_non_king_all<M: MoveSink>(&self, moves: &mut M) {
+        let pinned = self.pinned();
         let target = !self.us(); // Can move to empty or enemy squares
         
         self.generate_pawn_moves(moves, Bitboard::UNIVERSE, pinned);
+        if moves.should_stop() {
+            return;
+        }
         self.generate_knight_moves(moves, target, pinned);
         self.generate_bishop_moves(moves, target, pinned);
         self.generate_rook_moves(moves, target, pinned);
         self.generate_queen_moves(moves, target, pinned);
-        self.generate_king_moves(moves);
+    }
+
+    /// Get the legal destinations of the piece on `sq`, split into
+    /// (quiet targets, capture targets). Castling lands in the quiet set
+    /// and en passant in the capture set. Both are empty if `sq` does not
+    /// hold a piece of the side to move.
+    pub fn piece_targets(&self, sq: Square) -> (Bitboard, Bitboard) {
+        let mut quiets = Bitboard::EMPTY;
+        let mut captures = Bitboard::EMPTY;
+        for m in self.moves_from(sq).iter() {
+            if m.is_capture() {
+                captures |= Bitboard::from_square(m.to());
+            } else {
+                quiets |= Bitboard::from_square(m.to());
+            }
+        }
+        (quiets, captures)
+    }
+
+    /// Generate all legal moves landing on `sq`.
+    pub fn moves_to(&self, sq: Square) -> MoveList {
+        let mut moves = MoveList::new();
+        let mut sink = LandingOn(&mut moves, sq);
+        if !self.checkers().more_than_one() {
+            let pinned = self.pinned();
+            let check_mask = self.check_mask();
+            let target = check_mask & Bitboard::from_square(sq) & !self.us();
+
+            // Pawns get the whole check mask: en passant can capture a checker
+            // without landing on its square
+            self.generate_pawn_moves(&mut sink, check_mask, pinned);
+            if target.any() {
+                self.generate_knight_moves(&mut sink, target, pinned);
+                self.generate_bishop_moves(&mut sink, target, pinned);
+                self.generate_rook_moves(&mut sink, target, pinned);
+                self.generate_queen_moves(&mut sink, target, pinned);
+            }
+        }
+        self.generate_king_moves(&mut sink);
+        moves
+    }
+
+    /// Generate the legal moves starting on `sq`. Empty unless `sq` holds a
+    /// piece of the side to move.
+    pub fn moves_from(&self, sq: Square) -> MoveList {
+        let mut moves = MoveList::new();
+        self.generate_moves_from(sq, &mut moves);
+        moves
+    }
+
+    /// Count the legal moves of the piece on `sq`, or 0 if it is empty.
+    ///
+    /// A piece of the side not to move is counted as if it were its turn.
+    pub fn mobility(&self, sq: Square) -> u32 {
+        if self.them().contains(sq) {
+            return self.make_null_move().mobility(sq);
+        }
+        let mut counter = MoveCounter::new();
+        self.generate_moves_from(sq, &mut counter);
+        counter.count as u32
+    }
+
+    /// Internal: legal moves of the side to move starting on `sq`. Only the
+    /// generator for the piece standing there is run.
+    fn generate_moves_from<M: MoveSink>(&self, sq: Square, moves: &mut M) {
+        let Some((piece, color)) = self.piece_at(sq) else {
+            return;
+        };
+        if color != self.turn() {
+            return;
+        }
+
+        let mut sink = LeavingFrom(moves, sq);
+        if piece == Piece::King {
+            self.generate_king_moves(&mut sink);
+        } else if !self.checkers().more_than_one() {
+            let pinned = self.pinned();
+            let check_mask = self.check_mask();
+            let target = check_mask & !self.us();
+            match piece {
+                Piece::Pawn => self.generate_pawn_moves(&mut sink, check_mask, pinned),
+                Piece::Knight => self.generate_knight_moves(&mut sink, target, pinned),
+                Piece::Bishop => self.generate_bishop_moves(&mut sink, target, pinned),
+                Piece::Rook => self.generate_rook_moves(&mut sink, target, pinned),
+                Piece::Queen => self.generate_queen_moves(&mut sink, target, pinned),
+                Piece::King => unreachable!(),
+            }
+        }
+    }
+
+    /// Count the legal moves of `color`, as if it were its turn.
+    pub fn mobility_for(&self, color: Color) -> u32 {
+        if color == self.turn() {
+            self.generate_moves_count() as u32
+        } else {
+            self.make_null_move().generate_moves_count() as u32
+        }
+    }
+
+    /// Check if the side to move is not in check and has no captures or
+    /// promotions available (the quiescence stand-pat condition).
+    pub fn is_quiet_position(&self) -> bool {
+        if self.in_check() {
+            return false;
+        }
+
+        let mut finder = TacticalFinder { found: false };
+        let pinned = self.pinned();
+        let target = self.them();
+
+        // Pawns get the full mask so quiet push-promotions are seen too
+        self.generate_pawn_moves(&mut finder, Bitboard::UNIVERSE, pinned);
+        if finder.found {
+            return false;
+        }
+        self.generate_knight_moves(&mut finder, target, pinned);
+        self.generate_bishop_moves(&mut finder, target, pinned);
+        self.generate_rook_moves(&mut finder, target, pinned);
+        self.generate_queen_moves(&mut finder, target, pinned);
+        if finder.found {
+            return false;
+        }
+        self.generate_king_moves(&mut finder);
+        !finder.found
     }
 
     /// Generate only capture moves (staged generation).
@@ -77,28 +270,23 @@ impl Board {
     }
 
     /// Generate only quiet moves (non-captures, staged generation).
+    /// Includes castling and push promotions.
     pub fn generate_quiets(&self) -> MoveList {
         let mut moves = MoveList::new();
+        let mut quiets = QuietsOnly(&mut moves);
         if self.checkers().more_than_one() {
-            self.generate_king_moves(&mut moves);
+            self.generate_king_moves(&mut quiets);
         } else if self.checkers().any() {
-            self.generate_evasions(&mut moves);
+            self.generate_evasions(&mut quiets);
         } else {
-            self.generate_quiets_impl(&mut moves);
+            self.generate_quiets_impl(&mut quiets);
         }
-        // Filter to only quiets
-        let mut result = MoveList::new();
-        for m in moves.iter() {
-            if !m.is_capture() {
-                result.push(m);
-            }
-        }
-        result
+        moves
     }
 
     /// Internal: generate captures only.
     fn generate_captures_impl<M: MoveSink>(&self, moves: &mut M) {
-        let pinned = self.compute_pinned();
+        let pinned = self.pinned();
         let target = self.them(); // Only enemy squares
         
         self.generate_pawn_moves(moves, target, pinned);
@@ -111,10 +299,10 @@ impl Board {
 
     /// Internal: generate quiets only.
     fn generate_quiets_impl<M: MoveSink>(&self, moves: &mut M) {
-        let pinned = self.compute_pinned();
+        let pinned = self.pinned();
         let target = !self.occupied(); // Only empty squares
         
-        self.generate_pawn_moves(moves, target, pinned);
+        self.generate_pawn_pushes(moves, target, pinned);
         self.generate_knight_moves(moves, target, pinned);
         self.generate_bishop_moves(moves, target, pinned);
         self.generate_rook_moves(moves, target, pinned);
@@ -124,15 +312,26 @@ impl Board {
 
     /// Generate moves when in check.
     fn generate_evasions<M: MoveSink>(&self, moves: &mut M) {
-        let king_sq = self.king_square(self.turn());
-        let checker_sq = unsafe { self.checkers().lsb_unchecked() };
+        self.generate_non_king_evasions(moves);
+        if moves.should_stop() {
+            return;
+        }
         
+        // King moves (always generated)
+        self.generate_king_moves(moves);
+    }
+
+    /// Internal: non-king moves that block or capture a single checker.
+    fn generate_non_king_evasions<M: MoveSink>(&self, moves: &mut M) {
         // Squares that block or capture the checker
-        let block_mask = between(king_sq, checker_sq) | self.checkers();
-        let pinned = self.compute_pinned();
+        let block_mask = self.check_mask();
+        let pinned = self.pinned();
         
         // Pawn moves that block/capture
         self.generate_pawn_moves(moves, block_mask, pinned);
+        if moves.should_stop() {
+            return;
+        }
         
         // Knight moves that block/capture
         self.generate_knight_moves(moves, block_mask & !self.us(), pinned);
@@ -141,19 +340,39 @@ impl Board {
         self.generate_bishop_moves(moves, block_mask & !self.us(), pinned);
         self.generate_rook_moves(moves, block_mask & !self.us(), pinned);
         self.generate_queen_moves(moves, block_mask & !self.us(), pinned);
-        
-        // King moves (always generated)
-        self.generate_king_moves(moves);
+    }
+
+    /// Squares a non-king move must land on to resolve check.
+    ///
+    /// Every square when not in check, the checker and the squares between it
+    /// and the king in single check, and no squares in double check.
+    pub fn check_mask(&self) -> Bitboard {
+        match self.check_type() {
+            CheckType::None => Bitboard::UNIVERSE,
+            CheckType::Double => Bitboard::EMPTY,
+            CheckType::Single(checker) => {
+                between(self.king_square(self.turn()), checker) | Bitboard::from_square(checker)
+            }
+        }
     }
 
     /// Compute pinned pieces.
+    #[inline]
     pub fn compute_pinned(&self) -> Bitboard {
+        self.pinned_with_pinners().0
+    }
+
+    /// Compute pinned pieces of the side to move and the enemy sliders pinning them.
+    ///
+    /// The ray of a pinned piece is `line(king, pinner)` for the pinner it shares a line with.
+    pub fn pinned_with_pinners(&self) -> (Bitboard, Bitboard) {
         let king_sq = self.king_square(self.turn());
         let occ = self.occupied();
         let us = self.us();
         let them = self.them();
         
         let mut pinned = Bitboard::EMPTY;
+        let mut pinners = Bitboard::EMPTY;
         
         // Diagonal pinners
         let diag_sliders = (self.piece_bb(Piece::Bishop) | self.piece_bb(Piece::Queen)) & them;
@@ -164,6 +383,7 @@ impl Board {
             let blockers = between_bb & occ;
             if blockers.exactly_one() && (blockers & us).any() {
                 pinned |= blockers;
+                pinners |= Bitboard::from_square(pinner);
             }
         }
         
@@ -176,10 +396,11 @@ impl Board {
             let blockers = between_bb & occ;
             if blockers.exactly_one() && (blockers & us).any() {
                 pinned |= blockers;
+                pinners |= Bitboard::from_square(pinner);
             }
         }
         
-        pinned
+        (pinned, pinners)
     }
 
     /// Generate knight moves.
@@ -187,6 +408,9 @@ impl Board {
         let knights = self.piece_color_bb(Piece::Knight, self.turn()) & !pinned;
         
         for from in knights {
+            if moves.should_stop() {
+                return;
+            }
             let attacks = knight_attacks(from) & target;
             for to in attacks {
                 let flag = if self.them().contains(to) {
@@ -206,6 +430,9 @@ impl Board {
         let king_sq = self.king_square(self.turn());
         
         for from in bishops {
+            if moves.should_stop() {
+                return;
+            }
             let mut attacks = bishop_attacks(from, occ) & target;
             
             // If pinned, can only move along pin ray
@@ -231,6 +458,9 @@ impl Board {
         let king_sq = self.king_square(self.turn());
         
         for from in rooks {
+            if moves.should_stop() {
+                return;
+            }
             let mut attacks = rook_attacks(from, occ) & target;
             
             if pinned.contains(from) {
@@ -255,6 +485,9 @@ impl Board {
         let king_sq = self.king_square(self.turn());
         
         for from in queens {
+            if moves.should_stop() {
+                return;
+            }
             let mut attacks = (bishop_attacks(from, occ) | rook_attacks(from, occ)) & target;
             
             if pinned.contains(from) {
@@ -299,59 +532,185 @@ impl Board {
         }
     }
 
+    /// Check whether `color` could castle kingside right now: the right is held,
+    /// the path is empty, the king is not in check, and it does not pass
+    /// through or land on an attacked square.
+    pub fn can_castle_kingside(&self, color: Color) -> bool {
+        self.castling().has_kingside(color)
+            && !self.is_attacked(self.king_square(color), !color)
+            && self.castle_path_safe(color, true)
+    }
+
+    /// Check whether `color` could castle queenside right now.
+    /// See [`Board::can_castle_kingside`] for the conditions.
+    pub fn can_castle_queenside(&self, color: Color) -> bool {
+        self.castling().has_queenside(color)
+            && !self.is_attacked(self.king_square(color), !color)
+            && self.castle_path_safe(color, false)
+    }
+
+    /// Check that the squares between king and rook are empty and the
+    /// squares the king crosses are not attacked by the opponent.
+    #[inline]
+    fn castle_path_safe(&self, color: Color, kingside: bool) -> bool {
+        if self.is_chess960() {
+            return self.castle_path_safe_960(color, kingside);
+        }
+
+        let occ = self.occupied();
+        let (path, cross, dest) = match (color, kingside) {
+            (Color::White, true) => (Bitboard::BETWEEN_E1_G1, Square::F1, Square::G1),
+            (Color::White, false) => (Bitboard::BETWEEN_E1_C1, Square::D1, Square::C1),
+            (Color::Black, true) => (Bitboard::BETWEEN_E8_G8, Square::F8, Square::G8),
+            (Color::Black, false) => (Bitboard::BETWEEN_E8_C8, Square::D8, Square::C8),
+        };
+        (occ & path).is_empty()
+            && !self.is_attacked(cross, !color)
+            && !self.is_attacked(dest, !color)
+    }
+
+    /// Chess960 version of `castle_path_safe` for arbitrary king and rook files.
+    /// Attacks are tested with both castling pieces lifted, so a rook that
+    /// was shielding the king's path from a slider does not count.
+    fn castle_path_safe_960(&self, color: Color, kingside: bool) -> bool {
+        let king = self.king_square(color);
+        let rook = self.castle_rook_square(color, kingside);
+        let (king_to, rook_to) = self.castle_destinations(color, kingside);
+
+        let occ = self.occupied() ^ Bitboard::from_square(king) ^ Bitboard::from_square(rook);
+        let king_path = between(king, king_to) | Bitboard::from_square(king_to);
+        let rook_path = between(rook, rook_to) | Bitboard::from_square(rook_to);
+        if (occ & (king_path | rook_path)).any() {
+            return false;
+        }
+
+        let them = self.color_bb(!color);
+        king_path.into_iter().all(|sq| (self.attackers_to(sq, occ) & them).is_empty())
+    }
+
     /// Generate castling moves.
     fn generate_castling_moves<M: MoveSink>(&self, moves: &mut M, king_sq: Square) {
         let us = self.turn();
-        let occ = self.occupied();
-        
-        if us == Color::White {
-            // Kingside
-            if self.castling().has_white_kingside() {
-                if (occ & Bitboard::BETWEEN_E1_G1).is_empty() {
-                    // Check intermediate squares not attacked
-                    if (self.attackers_to(Square::F1, occ) & self.them()).is_empty()
-                        && (self.attackers_to(Square::G1, occ) & self.them()).is_empty()
-                    {
-                        moves.push(Move::new(king_sq, Square::G1, MoveFlag::KingCastle));
-                    }
-                }
-            }
-            // Queenside
-            if self.castling().has_white_queenside() {
-                if (occ & Bitboard::BETWEEN_E1_C1).is_empty() {
-                    if (self.attackers_to(Square::D1, occ) & self.them()).is_empty()
-                        && (self.attackers_to(Square::C1, occ) & self.them()).is_empty()
-                    {
-                        moves.push(Move::new(king_sq, Square::C1, MoveFlag::QueenCastle));
-                    }
-                }
-            }
-        } else {
-            // Kingside
-            if self.castling().has_black_kingside() {
-                if (occ & Bitboard::BETWEEN_E8_G8).is_empty() {
-                    if (self.attackers_to(Square::F8, occ) & self.them()).is_empty()
-                        && (self.attackers_to(Square::G8, occ) & self.them()).is_empty()
-                    {
-                        moves.push(Move::new(king_sq, Square::G8, MoveFlag::KingCastle));
-                    }
-                }
-            }
-            // Queenside
-            if self.castling().has_black_queenside() {
-                if (occ & Bitboard::BETWEEN_E8_C8).is_empty() {
-                    if (self.attackers_to(Square::D8, occ) & self.them()).is_empty()
-                        && (self.attackers_to(Square::C8, occ) & self.them()).is_empty()
-                    {
-                        moves.push(Move::new(king_sq, Square::C8, MoveFlag::QueenCastle));
-                    }
-                }
-            }
+
+        // The caller has already ruled out check
+        if self.castling().has_kingside(us) && self.castle_path_safe(us, true) {
+            let (to, _) = self.castle_destinations(us, true);
+            moves.push(Move::new(king_sq, to, MoveFlag::KingCastle));
         }
+        if self.castling().has_queenside(us) && self.castle_path_safe(us, false) {
+            let (to, _) = self.castle_destinations(us, false);
+            moves.push(Move::new(king_sq, to, MoveFlag::QueenCastle));
+        }
+    }
+}
+
+/// Sink that stops generation at the first move.
+struct FirstMove {
+    found: bool,
+}
+
+impl MoveSink for FirstMove {
+    #[inline(always)]
+    fn push(&mut self, _mv: Move) {
+        self.found = true;
+    }
+
+    #[inline(always)]
+    fn should_stop(&self) -> bool {
+        self.found
+    }
+}
+
+/// Sink that drops captures (the king generator emits both).
+struct QuietsOnly<'a, M: MoveSink>(&'a mut M);
+
+impl<M: MoveSink> MoveSink for QuietsOnly<'_, M> {
+    #[inline(always)]
+    fn push(&mut self, mv: Move) {
+        if !mv.is_capture() {
+            self.0.push(mv);
+        }
+    }
+}
+
+/// Sink that keeps only moves landing on one square.
+struct LandingOn<'a, M: MoveSink>(&'a mut M, Square);
+
+impl<M: MoveSink> MoveSink for LandingOn<'_, M> {
+    #[inline(always)]
+    fn push(&mut self, mv: Move) {
+        if mv.to() == self.1 {
+            self.0.push(mv);
+        }
+    }
+}
+
+/// Sink that keeps only moves starting on one square.
+struct LeavingFrom<'a, M: MoveSink>(&'a mut M, Square);
+
+impl<M: MoveSink> MoveSink for LeavingFrom<'_, M> {
+    #[inline(always)]
+    fn push(&mut self, mv: Move) {
+        if mv.from() == self.1 {
+            self.0.push(mv);
+        }
+    }
+}
+
+/// Sink that fills a borrowed slice, counting whatever does not fit.
+struct SliceSink<'a> {
+    buf: &'a mut [Move],
+    len: usize,
+}
+
+impl MoveSink for SliceSink<'_> {
+    #[inline(always)]
+    fn push(&mut self, mv: Move) {
+        if let Some(slot) = self.buf.get_mut(self.len) {
+            *slot = mv;
+        }
+        self.len += 1;
     }
 }
 
-#[cfg(test)]
+/// Sink that forwards every move except one.
+struct ExcludingSink<M: MoveSink> {
+    inner: M,
+    skip: Move,
+}
+
+impl<M: MoveSink> MoveSink for ExcludingSink<M> {
+    #[inline(always)]
+    fn push(&mut self, mv: Move) {
+        if mv != self.skip {
+            self.inner.push(mv);
+        }
+    }
+
+    #[inline(always)]
+    fn should_stop(&self) -> bool {
+        self.inner.should_stop()
+    }
+}
+
+/// Sink that only records whether a capture or promotion was pushed.
+struct TacticalFinder {
+    found: bool,
+}
+
+impl MoveSink for TacticalFinder {
+    #[inline(always)]
+    fn push(&mut self, mv: Move) {
+        self.found |= mv.is_capture() || mv.is_promotion();
+    }
+
+    #[inline(always)]
+    fn should_stop(&self) -> bool {
+        self.found
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
@@ -372,4 +731,243 @@ mod tests {
         // Kiwipete has 48 legal moves
         assert_eq!(moves.len(), 48);
     }
+
+    #[test]
+    fn test_non_king_moves() {
+        let board = Board::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+        ).unwrap();
+        let king_sq = board.king_square(board.turn());
+        let non_king = board.generate_non_king_moves();
+        assert!(non_king.iter().all(|m| m.from() != king_sq));
+
+        let king_moves = board.generate_moves().iter().filter(|m| m.from() == king_sq).count();
+        assert_eq!(non_king.len() + king_moves, board.generate_moves().len());
+    }
+
+    #[test]
+    fn test_generate_moves_slice() {
+        let board = Board::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+        ).unwrap();
+        let moves = board.generate_moves();
+        let mut buf = [Move::NULL; 64];
+        assert_eq!(board.generate_moves_slice(&mut buf), 48);
+        assert_eq!(&buf[..48], moves.as_slice());
+        assert_eq!(buf[48], Move::NULL);
+
+        let mut small = [Move::NULL; 10];
+        assert_eq!(board.generate_moves_slice(&mut small), 48);
+        assert_eq!(&small[..], &moves.as_slice()[..10]);
+        assert_eq!(board.generate_moves_slice(&mut []), 48);
+    }
+
+    #[test]
+    fn test_mobility() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            // Single check with an en passant evasion and a pinned knight
+            "8/8/3p4/1Pp4r/1K3p1k/8/4P1P1/1R6 w - c6 0 3",
+            "4r1k1/8/8/q7/4N3/2B5/8/4KBNr w - - 0 1",
+        ];
+        for fen in fens {
+            let board = Board::from_fen(fen).unwrap();
+            for color in [Color::White, Color::Black] {
+                let view = if color == board.turn() { board } else { board.make_null_move() };
+                let moves = view.generate_moves();
+                let mut total = 0;
+                for sq in board.color_bb(color) {
+                    let expected = moves.iter().filter(|m| m.from() == sq).count() as u32;
+                    assert_eq!(board.mobility(sq), expected, "{fen} {sq}");
+                    if color == board.turn() {
+                        let from: Vec<_> = board.moves_from(sq).iter().collect();
+                        let filtered: Vec<_> = moves.iter().filter(|m| m.from() == sq).collect();
+                        assert_eq!(from, filtered, "{fen} {sq}");
+                    } else {
+                        assert!(board.moves_from(sq).is_empty());
+                    }
+                    total += expected;
+                }
+                assert_eq!(board.mobility_for(color), total);
+            }
+        }
+        assert_eq!(Board::startpos().mobility(Square::E4), 0);
+    }
+
+    #[test]
+    fn test_piece_targets_pinned_rook() {
+        // White rook on e4 is pinned to the king on e1 by the rook on e7
+        let board = Board::from_fen("4k3/4r3/8/8/4R3/8/8/4K3 w - - 0 1").unwrap();
+        let (quiets, captures) = board.piece_targets(Square::E4);
+        assert_eq!(captures, Bitboard::from_square(Square::E7));
+        assert_eq!(quiets.count(), 4); // e2, e3, e5, e6
+        assert!((quiets & !Bitboard::FILE_E).is_empty());
+        assert_eq!(board.piece_targets(Square::E7), (Bitboard::EMPTY, Bitboard::EMPTY));
+    }
+
+    #[test]
+    fn test_is_quiet_position() {
+        // White knight can take the loose pawn on d6
+        let board = Board::from_fen("4k3/8/3p4/8/4N3/8/8/4K3 w - - 0 1").unwrap();
+        assert!(!board.is_quiet_position());
+
+        // Locked pawns, nothing to capture
+        let board = Board::from_fen("4k3/8/8/4p3/4P3/8/8/4K3 w - - 0 1").unwrap();
+        assert!(board.is_quiet_position());
+
+        // A push-promotion is not quiet
+        let board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert!(!board.is_quiet_position());
+    }
+
+    #[test]
+    fn test_generate_moves_excluding() {
+        let board = Board::startpos();
+        let skip = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
+        let moves = board.generate_moves_excluding(skip);
+        assert!(!moves.contains(skip));
+        assert_eq!(moves.len(), board.generate_moves().len() - 1);
+    }
+
+    #[test]
+    fn test_generate_moves_into() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        let mut collected = Vec::new();
+        board.generate_moves_into(&mut ClosureSink(|mv| collected.push(mv)));
+        assert_eq!(collected, board.generate_moves().iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_moves_to() {
+        for fen in [
+            crate::testing::KIWIPETE,
+            "8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1",
+            "4r1k1/8/8/8/8/3n4/8/4K3 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            let all = board.generate_moves();
+            for sq in Bitboard::UNIVERSE {
+                let mut expected: Vec<Move> = all.iter().filter(|m| m.to() == sq).collect();
+                let mut got: Vec<Move> = board.moves_to(sq).iter().collect();
+                expected.sort_by_key(|m| m.bits());
+                got.sort_by_key(|m| m.bits());
+                assert_eq!(got, expected, "{} {:?}", fen, sq);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pinned_with_pinners() {
+        // Knight on e4 pinned by the rook on e8, bishop on c3 by the queen on a5;
+        // the rook on h1 sees two pieces and pins neither
+        let board = Board::from_fen("4r1k1/8/8/q7/4N3/2B5/8/4KBNr w - - 0 1").unwrap();
+        let (pinned, pinners) = board.pinned_with_pinners();
+        assert_eq!(pinned, Bitboard::from_square(Square::E4) | Bitboard::from_square(Square::C3));
+        assert_eq!(pinners, Bitboard::from_square(Square::E8) | Bitboard::from_square(Square::A5));
+        assert_eq!(board.compute_pinned(), pinned);
+    }
+
+    #[test]
+    fn test_check_mask() {
+        assert_eq!(Board::startpos().check_mask(), Bitboard::UNIVERSE);
+
+        // Rook on e8 checks the king on e1
+        let board = Board::from_fen("4r1k1/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let mask = board.check_mask();
+        assert_eq!(mask.count(), 7);
+        assert!(mask.contains(Square::E8) && mask.contains(Square::E2));
+        assert!(!mask.contains(Square::E1));
+
+        // Double check from rook and knight
+        let board = Board::from_fen("4r1k1/8/8/8/8/3n4/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(board.check_mask(), Bitboard::EMPTY);
+    }
+
+    #[test]
+    fn test_has_legal_moves() {
+        assert!(Board::startpos().has_legal_moves());
+        // Checkmated and stalemated kings
+        let mated = Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3").unwrap();
+        assert!(!mated.has_legal_moves());
+        let stalemated = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        assert!(!stalemated.has_legal_moves());
+        // Only a pawn can move
+        let pawn_only = Board::from_fen("7k/5Q2/6K1/8/8/8/P7/8 b - - 0 1").unwrap();
+        assert!(!pawn_only.has_legal_moves());
+        let pawn_only = Board::from_fen("k7/8/8/8/8/p7/8/KR6 w - - 0 1").unwrap();
+        assert!(pawn_only.has_legal_moves());
+    }
+
+    #[test]
+    fn test_generate_quiets() {
+        use crate::testing::{KIWIPETE, POSITION_4};
+        for fen in [KIWIPETE, POSITION_4, "4k3/1P6/8/3pP3/8/8/8/R3K3 w Q d6 0 1"] {
+            let board = Board::from_fen(fen).unwrap();
+            let all = board.generate_moves();
+            let quiets = board.generate_quiets();
+            assert!(quiets.iter().all(|m| !m.is_capture() && all.contains(m)));
+            assert_eq!(quiets.len(), all.iter().filter(|m| !m.is_capture()).count());
+        }
+    }
+
+    #[test]
+    fn test_chess960_perft() {
+        use crate::testing::perft;
+        let cases: [(&str, [u64; 3]); 4] = [
+            ("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9", [21, 528, 12189]),
+            ("2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9", [21, 807, 18002]),
+            ("b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9", [20, 479, 10471]),
+            ("qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9", [22, 593, 13440]),
+        ];
+        for (fen, counts) in cases {
+            let board = Board::from_fen(fen).unwrap();
+            assert!(board.is_chess960());
+            for (depth, &nodes) in counts.iter().enumerate() {
+                assert_eq!(perft(&board, depth as u32 + 1), nodes, "{} depth {}", fen, depth + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chess960_castling() {
+        // King f1 castles queenside with the rook on b1
+        let mut board = Board::from_fen("7k/8/8/8/8/8/8/1R3K2 w B - 0 1").unwrap();
+        let castle = Move::new(Square::F1, Square::C1, MoveFlag::QueenCastle);
+        assert!(board.generate_moves().contains(castle));
+        let undo = board.make_move(castle);
+        assert_eq!(board.piece_at(Square::C1), Some((Piece::King, Color::White)));
+        assert_eq!(board.piece_at(Square::D1), Some((Piece::Rook, Color::White)));
+        assert_eq!(board.hash(), board.recompute_hash());
+        board.unmake_move(castle, undo);
+        assert_eq!(board.piece_at(Square::B1), Some((Piece::Rook, Color::White)));
+
+        // The rook on b1 shields c1 from the queen only until it moves
+        let board = Board::from_fen("7k/8/8/8/8/8/8/qR3K2 w B - 0 1").unwrap();
+        assert!(!board.generate_moves().contains(castle));
+
+        // King already on g1 with the rook on h1: castling only moves the rook
+        let board = Board::from_fen("7k/8/8/8/8/8/8/6KR w H - 0 1").unwrap();
+        let castle = Move::new(Square::G1, Square::G1, MoveFlag::KingCastle);
+        assert!(board.generate_moves().contains(castle));
+        let after = board.make_move_new(castle);
+        assert_eq!(after.piece_at(Square::G1), Some((Piece::King, Color::White)));
+        assert_eq!(after.piece_at(Square::F1), Some((Piece::Rook, Color::White)));
+        assert_eq!(after.hash(), after.recompute_hash());
+    }
+
+    #[test]
+    fn test_can_castle() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        for color in Color::ALL {
+            assert!(board.can_castle_kingside(color));
+            assert!(board.can_castle_queenside(color));
+        }
+
+        // Black rook on f8 covers f1: white kingside path is attacked
+        let board = Board::from_fen("r3kr2/8/8/8/8/8/8/R3K2R w KQq - 0 1").unwrap();
+        assert!(!board.can_castle_kingside(Color::White));
+        assert!(board.can_castle_queenside(Color::White));
+        assert!(!board.can_castle_kingside(Color::Black));
+    }
 }
@@ -1,6 +1,7 @@
 //! Move type and MoveList container.
 
 use core::fmt;
+use crate::board::Board;
 use crate::types::{Square, Piece};
 
 /// Move flags for special move types.
@@ -73,7 +74,10 @@ impl MoveFlag {
 /// - Bits 0-5: Source square (0-63)
 /// - Bits 6-11: Destination square (0-63)
 /// - Bits 12-15: Move flag
-#[derive(Clone, Copy, PartialEq, Eq, Default)]
+///
+/// `Hash` and `Ord` are defined on the raw 16-bit encoding, so they are
+/// stable across runs and versions that keep this layout.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[repr(transparent)]
 pub struct Move(u16);
 
@@ -127,6 +131,18 @@ impl Move {
         self.flag().is_promotion()
     }
 
+    /// Check if this is an en passant capture.
+    #[inline(always)]
+    pub const fn is_en_passant(self) -> bool {
+        matches!(self.flag(), MoveFlag::EnPassant)
+    }
+
+    /// Check if this is a castling move.
+    #[inline(always)]
+    pub const fn is_castle(self) -> bool {
+        matches!(self.flag(), MoveFlag::KingCastle | MoveFlag::QueenCastle)
+    }
+
     /// Get raw bits.
     #[inline(always)]
     pub const fn bits(self) -> u16 {
@@ -139,39 +155,106 @@ impl Move {
         Move(bits)
     }
 
-    /// Convert to UCI string.
+    /// Convert to UCI string. The null move is written as `0000`.
+    ///
+    /// Castling is written as the king's move, which is wrong for Chess960;
+    /// use [`Board::move_to_uci`](crate::Board::move_to_uci) there.
+    #[cfg(feature = "std")]
     pub fn to_uci(self) -> String {
-        let from = self.from().to_algebraic();
-        let to = self.to().to_algebraic();
-        let mut s = String::with_capacity(5);
-        s.push(from[0]);
-        s.push(from[1]);
-        s.push(to[0]);
-        s.push(to[1]);
-        
-        if let Some(promo) = self.flag().promotion_piece() {
-            s.push(match promo {
-                Piece::Knight => 'n',
-                Piece::Bishop => 'b',
-                Piece::Rook => 'r',
-                Piece::Queen => 'q',
-                _ => 'q',
-            });
+        let mut buf = [0u8; 5];
+        let len = self.write_uci(&mut buf);
+        buf[..len].iter().map(|&b| b as char).collect()
+    }
+
+    /// Write the UCI string into `buf` without allocating and return its length (4 or 5).
+    ///
+    /// # Panics
+    /// Panics if `buf` is too short; 5 bytes always suffice.
+    pub fn write_uci(self, buf: &mut [u8]) -> usize {
+        if self == Move::NULL {
+            buf[..4].copy_from_slice(b"0000");
+            return 4;
+        }
+        let [from_file, from_rank] = self.from().to_algebraic();
+        let [to_file, to_rank] = self.to().to_algebraic();
+        buf[..4].copy_from_slice(&[from_file as u8, from_rank as u8, to_file as u8, to_rank as u8]);
+
+        match self.flag().promotion_piece() {
+            Some(promo) => {
+                buf[4] = match promo {
+                    Piece::Knight => b'n',
+                    Piece::Bishop => b'b',
+                    Piece::Rook => b'r',
+                    _ => b'q',
+                };
+                5
+            }
+            None => 4,
         }
-        
-        s
+    }
+
+    /// Format as UCI without allocating.
+    fn fmt_uci(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; 5];
+        let len = self.write_uci(&mut buf);
+        f.write_str(core::str::from_utf8(&buf[..len]).map_err(|_| fmt::Error)?)
     }
 }
 
 impl fmt::Debug for Move {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Move({})", self.to_uci())
+        f.write_str("Move(")?;
+        self.fmt_uci(f)?;
+        f.write_str(")")
     }
 }
 
 impl fmt::Display for Move {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_uci())
+        self.fmt_uci(f)
+    }
+}
+
+/// Error returned when parsing a [`Move`] from a string fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseMoveError;
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid UCI move")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseMoveError {}
+
+impl core::str::FromStr for Move {
+    type Err = ParseMoveError;
+
+    /// Parse coordinate notation such as `e2e4` or `e7e8q`; `0000` is the null move.
+    ///
+    /// Without a board the flag is a guess: promotions get a quiet
+    /// promotion flag and everything else is `Quiet`, so captures, double
+    /// pushes, en passant and castling come out wrong. Use
+    /// [`Board::parse_uci`](crate::Board::parse_uci) for the exact move.
+    fn from_str(s: &str) -> Result<Move, ParseMoveError> {
+        if s == "0000" {
+            return Ok(Move::NULL);
+        }
+        if !s.is_ascii() || !(4..=5).contains(&s.len()) {
+            return Err(ParseMoveError);
+        }
+        let from = Square::from_algebraic(&s[0..2]).ok_or(ParseMoveError)?;
+        let to = Square::from_algebraic(&s[2..4]).ok_or(ParseMoveError)?;
+        let flag = match s.as_bytes().get(4) {
+            None => MoveFlag::Quiet,
+            Some(b'n') => MoveFlag::PromoKnight,
+            Some(b'b') => MoveFlag::PromoBishop,
+            Some(b'r') => MoveFlag::PromoRook,
+            Some(b'q') => MoveFlag::PromoQueen,
+            Some(_) => return Err(ParseMoveError),
+        };
+        Ok(Move::new(from, to, flag))
     }
 }
 
@@ -189,18 +272,35 @@ impl ScoredMove {
     }
 }
 
+/// Error returned by [`MoveList::try_push`] when the list is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveListFull;
+
+impl fmt::Display for MoveListFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move list is full ({} moves)", MoveList::CAPACITY)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MoveListFull {}
+
 /// Fixed-size move list (stack allocated).
+#[derive(Clone)]
 pub struct MoveList {
-    moves: [Move; 256],
+    moves: [Move; MoveList::CAPACITY],
     count: usize,
 }
 
 impl MoveList {
+    /// Maximum number of moves the list can hold.
+    pub const CAPACITY: usize = 256;
+
     /// Create an empty move list.
     #[inline]
     pub const fn new() -> MoveList {
         MoveList {
-            moves: [Move::NULL; 256],
+            moves: [Move::NULL; Self::CAPACITY],
             count: 0,
         }
     }
@@ -217,14 +317,34 @@ impl MoveList {
         self.count == 0
     }
 
+    /// Check if the list holds [`MoveList::CAPACITY`] moves.
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.count == Self::CAPACITY
+    }
+
     /// Push a move.
+    ///
+    /// # Panics
+    /// Panics if the list is full, in release builds too.
     #[inline(always)]
     pub fn push(&mut self, mv: Move) {
-        debug_assert!(self.count < 256);
+        assert!(self.count < Self::CAPACITY, "MoveList overflow");
         self.moves[self.count] = mv;
         self.count += 1;
     }
 
+    /// Push a move, or return an error if the list is full.
+    #[inline(always)]
+    pub fn try_push(&mut self, mv: Move) -> Result<(), MoveListFull> {
+        if self.is_full() {
+            return Err(MoveListFull);
+        }
+        self.moves[self.count] = mv;
+        self.count += 1;
+        Ok(())
+    }
+
     /// Get a move by index.
     #[inline(always)]
     pub fn get(&self, index: usize) -> Option<Move> {
@@ -247,12 +367,86 @@ impl MoveList {
         self.count = 0;
     }
 
+    /// Keep only the moves for which `f` returns `true`, preserving order.
+    pub fn retain(&mut self, mut f: impl FnMut(Move) -> bool) {
+        let mut kept = 0;
+        for i in 0..self.count {
+            let mv = self.moves[i];
+            if f(mv) {
+                self.moves[kept] = mv;
+                kept += 1;
+            }
+        }
+        self.count = kept;
+    }
+
+    /// Remove consecutive repeated moves, like [`Vec::dedup`].
+    pub fn dedup(&mut self) {
+        let mut prev = None;
+        self.retain(|mv| prev.replace(mv) != Some(mv));
+    }
+
+    /// Sort moves by ascending key with a stable insertion sort.
+    pub fn sort_by_key<K: Ord>(&mut self, mut f: impl FnMut(Move) -> K) {
+        let moves = self.as_slice_mut();
+        for i in 1..moves.len() {
+            let mv = moves[i];
+            let key = f(mv);
+            let mut j = i;
+            while j > 0 && f(moves[j - 1]) > key {
+                moves[j] = moves[j - 1];
+                j -= 1;
+            }
+            moves[j] = mv;
+        }
+    }
+
+    /// Sort moves by descending score, where `scores[i]` belongs to the
+    /// `i`-th move. Ties keep their order; `scores` itself is not reordered.
+    ///
+    /// # Panics
+    /// Panics if `scores` is shorter than the list.
+    pub fn sort_scored(&mut self, scores: &[i16]) {
+        let mut keys = [0i16; Self::CAPACITY];
+        keys[..self.count].copy_from_slice(&scores[..self.count]);
+        for i in 1..self.count {
+            let (mv, score) = (self.moves[i], keys[i]);
+            let mut j = i;
+            while j > 0 && keys[j - 1] < score {
+                self.moves[j] = self.moves[j - 1];
+                keys[j] = keys[j - 1];
+                j -= 1;
+            }
+            self.moves[j] = mv;
+            keys[j] = score;
+        }
+    }
+
     /// Iterate over moves.
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = Move> + '_ {
         self.moves[..self.count].iter().copied()
     }
 
+    /// Iterate over moves paired with the type of the piece making them.
+    ///
+    /// Generated moves come grouped by origin square, so `board` is probed
+    /// once per origin rather than once per move.
+    pub fn iter_with_pieces<'a>(&'a self, board: &'a Board) -> impl Iterator<Item = (Move, Piece)> + 'a {
+        let mut last: Option<(Square, Piece)> = None;
+        self.iter().map(move |mv| {
+            let piece = match last {
+                Some((from, piece)) if from == mv.from() => piece,
+                _ => {
+                    let piece = board.move_piece_kind(mv);
+                    last = Some((mv.from(), piece));
+                    piece
+                }
+            };
+            (mv, piece)
+        })
+    }
+
     /// Get as a slice.
     #[inline(always)]
     pub fn as_slice(&self) -> &[Move] {
@@ -279,7 +473,7 @@ impl Default for MoveList {
 
 impl IntoIterator for MoveList {
     type Item = Move;
-    type IntoIter = core::iter::Take<core::array::IntoIter<Move, 256>>;
+    type IntoIter = core::iter::Take<core::array::IntoIter<Move, { MoveList::CAPACITY }>>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.moves.into_iter().take(self.count)
@@ -301,9 +495,121 @@ impl fmt::Debug for MoveList {
     }
 }
 
+/// A small set of moves, kept sorted in a [`MoveList`].
+///
+/// Suited to per-node bookkeeping such as searched or excluded moves.
+#[derive(Clone, Default)]
+pub struct MoveSet {
+    list: MoveList,
+}
+
+impl MoveSet {
+    /// Create an empty set.
+    #[inline]
+    pub const fn new() -> MoveSet {
+        MoveSet { list: MoveList::new() }
+    }
+
+    /// Get the number of moves.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Check if empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Check if a move is in the set.
+    #[inline]
+    pub fn contains(&self, mv: Move) -> bool {
+        self.list.as_slice().binary_search(&mv).is_ok()
+    }
+
+    /// Insert a move. Returns `true` if it was not already present.
+    pub fn insert(&mut self, mv: Move) -> bool {
+        match self.list.as_slice().binary_search(&mv) {
+            Ok(_) => false,
+            Err(pos) => {
+                self.list.push(mv);
+                self.list.as_slice_mut()[pos..].rotate_right(1);
+                true
+            }
+        }
+    }
+
+    /// Remove a move. Returns `true` if it was present.
+    pub fn remove(&mut self, mv: Move) -> bool {
+        match self.list.as_slice().binary_search(&mv) {
+            Ok(pos) => {
+                self.list.as_slice_mut()[pos..].rotate_left(1);
+                self.list.count -= 1;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Remove all moves.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// Iterate over moves in ascending encoding order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Move> + '_ {
+        self.list.iter()
+    }
+
+    /// Moves in either set.
+    pub fn union(&self, other: &MoveSet) -> MoveSet {
+        let mut result = self.clone();
+        for mv in other.iter() {
+            result.insert(mv);
+        }
+        result
+    }
+
+    /// Moves in both sets.
+    pub fn intersection(&self, other: &MoveSet) -> MoveSet {
+        self.iter().filter(|&mv| other.contains(mv)).collect()
+    }
+
+    /// Moves in this set but not in `other`.
+    pub fn difference(&self, other: &MoveSet) -> MoveSet {
+        self.iter().filter(|&mv| !other.contains(mv)).collect()
+    }
+}
+
+impl FromIterator<Move> for MoveSet {
+    fn from_iter<I: IntoIterator<Item = Move>>(iter: I) -> MoveSet {
+        let mut set = MoveSet::new();
+        for mv in iter {
+            set.insert(mv);
+        }
+        set
+    }
+}
+
+impl fmt::Debug for MoveSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
 /// Trait for move collection (allows bulk counting without storing).
 pub trait MoveSink {
     fn push(&mut self, mv: Move);
+
+    /// Return `true` to ask the generator to stop early.
+    /// Checked between pieces, so a few more moves may still be pushed.
+    #[inline(always)]
+    fn should_stop(&self) -> bool {
+        false
+    }
 }
 
 impl MoveSink for MoveList {
@@ -324,6 +630,12 @@ impl MoveCounter {
     }
 }
 
+impl Default for MoveCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MoveSink for MoveCounter {
     #[inline(always)]
     fn push(&mut self, _mv: Move) {
@@ -331,10 +643,51 @@ impl MoveSink for MoveCounter {
     }
 }
 
-#[cfg(test)]
+/// A sink that hands every move to a closure.
+///
+/// ```
+/// use movegen::{Move, MoveFlag, Square};
+/// use movegen::movegen::{ClosureSink, MoveSink};
+///
+/// let mut targets = Vec::new();
+/// let mut sink = ClosureSink(|mv: Move| targets.push(mv.to()));
+/// sink.push(Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush));
+/// assert_eq!(targets, [Square::E4]);
+/// ```
+pub struct ClosureSink<F: FnMut(Move)>(pub F);
+
+impl<F: FnMut(Move)> MoveSink for ClosureSink<F> {
+    #[inline(always)]
+    fn push(&mut self, mv: Move) {
+        (self.0)(mv);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_move_list_capacity() {
+        let mv = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
+        let mut list = MoveList::new();
+        for _ in 0..MoveList::CAPACITY {
+            assert_eq!(list.try_push(mv), Ok(()));
+        }
+        assert!(list.is_full());
+        assert_eq!(list.try_push(mv), Err(MoveListFull));
+        assert_eq!(list.len(), MoveList::CAPACITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "MoveList overflow")]
+    fn test_move_list_overflow_panics() {
+        let mut list = MoveList::new();
+        for _ in 0..=MoveList::CAPACITY {
+            list.push(Move::NULL);
+        }
+    }
+
     #[test]
     fn test_move_encoding() {
         let mv = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
@@ -352,6 +705,34 @@ mod tests {
         assert_eq!(promo.to_uci(), "e7e8q");
     }
 
+    #[test]
+    fn test_move_from_str() {
+        assert_eq!("g1f3".parse(), Ok(Move::new(Square::G1, Square::F3, MoveFlag::Quiet)));
+        assert_eq!("e7e8q".parse(), Ok(Move::new(Square::E7, Square::E8, MoveFlag::PromoQueen)));
+        assert_eq!("e7e8k".parse::<Move>(), Err(ParseMoveError));
+        assert_eq!("e2".parse::<Move>(), Err(ParseMoveError));
+    }
+
+    #[test]
+    fn test_write_uci() {
+        let mut buf = [0u8; 5];
+        let mv = Move::new(Square::B7, Square::A8, MoveFlag::PromoKnightCapture);
+        assert_eq!(mv.write_uci(&mut buf), 5);
+        assert_eq!(&buf, b"b7a8n");
+        assert_eq!(format!("{:?}", mv), "Move(b7a8n)");
+
+        let mv = Move::new(Square::E1, Square::G1, MoveFlag::KingCastle);
+        assert_eq!(mv.write_uci(&mut buf), 4);
+        assert_eq!(&buf[..4], b"e1g1");
+    }
+
+    #[test]
+    fn test_null_move_uci() {
+        assert_eq!(Move::NULL.to_uci(), "0000");
+        assert_eq!(Move::NULL.to_string(), "0000");
+        assert_eq!("0000".parse(), Ok(Move::NULL));
+    }
+
     #[test]
     fn test_movelist() {
         let mut list = MoveList::new();
@@ -363,4 +744,78 @@ mod tests {
         list.push(Move::new(Square::D2, Square::D4, MoveFlag::DoublePawnPush));
         assert_eq!(list.len(), 2);
     }
+
+    #[test]
+    fn test_retain_and_dedup() {
+        let e4 = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
+        let d4 = Move::new(Square::D2, Square::D4, MoveFlag::DoublePawnPush);
+        let nf3 = Move::new(Square::G1, Square::F3, MoveFlag::Quiet);
+        let mut list = MoveList::new();
+        for mv in [e4, e4, d4, nf3, nf3, e4] {
+            list.push(mv);
+        }
+
+        list.dedup();
+        assert_eq!(list.as_slice(), [e4, d4, nf3, e4]);
+        list.retain(|mv| mv != e4);
+        assert_eq!(list.as_slice(), [d4, nf3]);
+        list.retain(|_| false);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_sort() {
+        let e4 = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
+        let d4 = Move::new(Square::D2, Square::D4, MoveFlag::DoublePawnPush);
+        let nf3 = Move::new(Square::G1, Square::F3, MoveFlag::Quiet);
+        let c4 = Move::new(Square::C2, Square::C4, MoveFlag::DoublePawnPush);
+        let mut list = MoveList::new();
+        for mv in [e4, d4, nf3, c4] {
+            list.push(mv);
+        }
+
+        list.sort_scored(&[10, -5, 30, 10, 99]);
+        assert_eq!(list.as_slice(), [nf3, e4, c4, d4]);
+        list.sort_by_key(|mv| mv.from().file());
+        assert_eq!(list.as_slice(), [c4, d4, e4, nf3]);
+        list.sort_by_key(|_| 0);
+        assert_eq!(list.as_slice(), [c4, d4, e4, nf3]);
+    }
+
+    #[test]
+    fn test_iter_with_pieces() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        let moves = board.generate_moves();
+        let pairs: Vec<_> = moves.iter_with_pieces(&board).collect();
+        assert_eq!(pairs.len(), moves.len());
+        for (mv, piece) in pairs {
+            assert_eq!(board.piece_at(mv.from()), Some((piece, board.turn())));
+        }
+    }
+
+    #[test]
+    fn test_moveset() {
+        let e4 = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
+        let d4 = Move::new(Square::D2, Square::D4, MoveFlag::DoublePawnPush);
+        let nf3 = Move::new(Square::G1, Square::F3, MoveFlag::Quiet);
+
+        let mut set = MoveSet::new();
+        assert!(set.insert(e4));
+        assert!(set.insert(nf3));
+        assert!(set.insert(d4));
+        assert!(!set.insert(e4));
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(d4));
+        assert!(set.iter().zip(set.iter().skip(1)).all(|(a, b)| a < b));
+
+        assert!(set.remove(d4));
+        assert!(!set.remove(d4));
+        assert!(!set.contains(d4));
+        assert!(set.contains(e4) && set.contains(nf3));
+
+        let other: MoveSet = [d4, e4].into_iter().collect();
+        assert_eq!(set.union(&other).len(), 3);
+        assert_eq!(set.intersection(&other).iter().collect::<Vec<_>>(), vec![e4]);
+        assert_eq!(set.difference(&other).iter().collect::<Vec<_>>(), vec![nf3]);
+    }
 }
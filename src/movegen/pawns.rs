@@ -4,15 +4,23 @@ use super::{Move, MoveFlag, MoveSink};
 use crate::bitboard::Bitboard;
 use crate::board::Board;
 use crate::types::{Color, Piece, Square, Rank};
-use crate::attacks::{pawn_attacks, line};
+use crate::attacks::{pawn_attacks, aligned};
 
 impl Board {
     /// Generate all pawn moves.
     pub fn generate_pawn_moves<M: MoveSink>(&self, moves: &mut M, target_mask: Bitboard, pinned: Bitboard) {
+        self.generate_pawn_pushes(moves, target_mask, pinned);
+        if moves.should_stop() {
+            return;
+        }
+        self.generate_pawn_captures(moves, target_mask, pinned);
+    }
+
+    /// Generate non-capturing pawn moves: pushes, double pushes and push promotions.
+    pub fn generate_pawn_pushes<M: MoveSink>(&self, moves: &mut M, target_mask: Bitboard, pinned: Bitboard) {
         let us = self.turn();
         let pawns = self.piece_color_bb(Piece::Pawn, us);
         let empty = self.empty_squares();
-        let enemies = self.them();
         let king_sq = self.king_square(us);
         
         let (push_dir, start_rank, promo_rank): (i8, Rank, Rank) = if us == Color::White {
@@ -32,15 +40,17 @@ impl Board {
             let from = unsafe { Square::from_index_unchecked((to.index() as i8 - push_dir) as u8) };
             
             // Check pin constraint
-            if pinned.contains(from) {
-                if !line(king_sq, from).contains(to) {
-                    continue;
-                }
+            if pinned.contains(from) && !aligned(king_sq, from, to) {
+                continue;
             }
             
             moves.push(Move::new(from, to, MoveFlag::Quiet));
         }
         
+        if moves.should_stop() {
+            return;
+        }
+        
         // Double pushes
         let double_push_pawns = pawns & Bitboard::rank_mask(start_rank);
         let single_step = if us == Color::White {
@@ -57,35 +67,19 @@ impl Board {
         for to in double_push_targets & target_mask {
             let from = unsafe { Square::from_index_unchecked((to.index() as i8 - 2 * push_dir) as u8) };
             
-            if pinned.contains(from) {
-                if !line(king_sq, from).contains(to) {
-                    continue;
-                }
+            if pinned.contains(from) && !aligned(king_sq, from, to) {
+                continue;
             }
             
             moves.push(Move::new(from, to, MoveFlag::DoublePawnPush));
         }
         
-        // Captures
-        for from in pawns & !Bitboard::rank_mask(promo_rank) {
-            let attacks = pawn_attacks(us, from) & enemies & target_mask;
-            
-            for to in attacks {
-                if pinned.contains(from) {
-                    if !line(king_sq, from).contains(to) {
-                        continue;
-                    }
-                }
-                
-                moves.push(Move::new(from, to, MoveFlag::Capture));
-            }
+        if moves.should_stop() {
+            return;
         }
         
-        // Promotions
-        let promo_pawns = pawns & Bitboard::rank_mask(promo_rank);
-        
-        for from in promo_pawns {
-            // Push promotion
+        // Push promotions
+        for from in pawns & Bitboard::rank_mask(promo_rank) {
             let to = if us == Color::White {
                 from.north()
             } else {
@@ -93,22 +87,55 @@ impl Board {
             };
             
             if let Some(to) = to {
-                if empty.contains(to) && target_mask.contains(to) {
-                    if !pinned.contains(from) || line(king_sq, from).contains(to) {
-                        self.add_promotions(moves, from, to, false);
-                    }
+                if empty.contains(to)
+                    && target_mask.contains(to)
+                    && (!pinned.contains(from) || aligned(king_sq, from, to))
+                {
+                    self.add_promotions(moves, from, to, false);
                 }
             }
+        }
+    }
+
+    /// Generate capturing pawn moves: captures, capture promotions and en passant.
+    pub fn generate_pawn_captures<M: MoveSink>(&self, moves: &mut M, target_mask: Bitboard, pinned: Bitboard) {
+        let us = self.turn();
+        let pawns = self.piece_color_bb(Piece::Pawn, us);
+        let enemies = self.them();
+        let king_sq = self.king_square(us);
+        let promo_rank = if us == Color::White { Rank::R7 } else { Rank::R2 };
+        
+        // Captures
+        for from in pawns & !Bitboard::rank_mask(promo_rank) {
+            let attacks = pawn_attacks(us, from) & enemies & target_mask;
             
-            // Capture promotions
+            for to in attacks {
+                if pinned.contains(from) && !aligned(king_sq, from, to) {
+                    continue;
+                }
+                
+                moves.push(Move::new(from, to, MoveFlag::Capture));
+            }
+        }
+        
+        if moves.should_stop() {
+            return;
+        }
+        
+        // Capture promotions
+        for from in pawns & Bitboard::rank_mask(promo_rank) {
             let attacks = pawn_attacks(us, from) & enemies & target_mask;
             for to in attacks {
-                if !pinned.contains(from) || line(king_sq, from).contains(to) {
+                if !pinned.contains(from) || aligned(king_sq, from, to) {
                     self.add_promotions(moves, from, to, true);
                 }
             }
         }
         
+        if moves.should_stop() {
+            return;
+        }
+        
         // En passant
         if let Some(ep_sq) = self.ep_square() {
             self.generate_en_passant(moves, ep_sq, pinned);
@@ -165,6 +192,7 @@ impl Board {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::movegen::MoveList;
 
     #[test]
     fn test_pawn_pushes() {
@@ -0,0 +1,45 @@
+//! Random legal move selection.
+
+use rand_core::RngCore;
+use super::Move;
+use crate::board::Board;
+
+impl Board {
+    /// Pick a uniformly random legal move, or `None` if there is none
+    /// (checkmate or stalemate).
+    pub fn random_move(&self, rng: &mut impl RngCore) -> Option<Move> {
+        let moves = self.generate_moves();
+        // Multiply-shift maps 64 random bits onto 0..len with negligible bias
+        let index = (rng.next_u64() as u128 * moves.len() as u128) >> 64;
+        moves.get(index as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_random_move_covers_all_moves() {
+        let board = Board::startpos();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut seen = Vec::new();
+        for _ in 0..1000 {
+            let mv = board.random_move(&mut rng).unwrap();
+            assert!(board.is_legal(mv));
+            if !seen.contains(&mv) {
+                seen.push(mv);
+            }
+        }
+        assert_eq!(seen.len(), 20);
+    }
+
+    #[test]
+    fn test_random_move_terminal() {
+        // Fool's mate
+        let board = Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3").unwrap();
+        assert_eq!(board.random_move(&mut StdRng::seed_from_u64(0)), None);
+    }
+}
@@ -0,0 +1,314 @@
+//! Standard Algebraic Notation (SAN) output.
+
+use core::fmt;
+use super::{Move, MoveFlag};
+use crate::board::Board;
+use crate::types::{Color, Piece, Square, File, Rank};
+
+/// Error returned by [`Board::parse_san`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SanError {
+    /// The string is not valid SAN.
+    Malformed,
+    /// No legal move matches.
+    Illegal,
+    /// More than one legal move matches.
+    Ambiguous,
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SanError::Malformed => write!(f, "malformed SAN"),
+            SanError::Illegal => write!(f, "illegal SAN move"),
+            SanError::Ambiguous => write!(f, "ambiguous SAN move"),
+        }
+    }
+}
+
+impl Board {
+    /// Convert a legal move to SAN (e.g., `Nf3`, `exd5`, `O-O`, `e8=Q+`).
+    ///
+    /// The move must be legal in this position; the check and mate
+    /// suffixes are found by playing it out.
+    pub fn move_to_san(&self, mv: Move) -> String {
+        let mut san = String::with_capacity(8);
+
+        match mv.flag() {
+            MoveFlag::KingCastle => san.push_str("O-O"),
+            MoveFlag::QueenCastle => san.push_str("O-O-O"),
+            _ => {
+                let from = mv.from();
+                let to = mv.to();
+                let piece = self.move_piece_kind(mv);
+
+                if piece == Piece::Pawn {
+                    if mv.is_capture() {
+                        san.push(from.file().to_char());
+                        san.push('x');
+                    }
+                } else {
+                    san.push(piece.to_char(Color::White));
+                    self.push_disambiguation(&mut san, mv, piece);
+                    if mv.is_capture() {
+                        san.push('x');
+                    }
+                }
+
+                let [file, rank] = to.to_algebraic();
+                san.push(file);
+                san.push(rank);
+
+                if let Some(promo) = mv.flag().promotion_piece() {
+                    san.push('=');
+                    san.push(promo.to_char(Color::White));
+                }
+            }
+        }
+
+        if self.gives_check(mv) {
+            san.push(if self.make_move_new(mv).has_legal_moves() { '+' } else { '#' });
+        }
+        san
+    }
+
+    /// Parse a SAN string (e.g., `Nbd7`, `exd6 e.p.`, `O-O-O`, `Qxe7+`)
+    /// into a legal move. Trailing `+`, `#`, `!` and `?` are ignored.
+    pub fn parse_san(&self, san: &str) -> Result<Move, SanError> {
+        let mut s = san.trim();
+        s = s.strip_suffix("e.p.").unwrap_or(s).trim_end();
+        s = s.trim_end_matches(['+', '#', '!', '?']);
+
+        // Castling
+        let castle = match s {
+            "O-O" | "0-0" => Some(MoveFlag::KingCastle),
+            "O-O-O" | "0-0-0" => Some(MoveFlag::QueenCastle),
+            _ => None,
+        };
+        if let Some(flag) = castle {
+            return self.generate_moves().iter()
+                .find(|m| m.flag() == flag)
+                .ok_or(SanError::Illegal);
+        }
+
+        let bytes = s.as_bytes();
+        if !s.is_ascii() || bytes.len() < 2 {
+            return Err(SanError::Malformed);
+        }
+
+        // Leading piece letter (pawns have none)
+        let (piece, mut rest) = match bytes[0] {
+            b'N' => (Piece::Knight, &s[1..]),
+            b'B' => (Piece::Bishop, &s[1..]),
+            b'R' => (Piece::Rook, &s[1..]),
+            b'Q' => (Piece::Queen, &s[1..]),
+            b'K' => (Piece::King, &s[1..]),
+            _ => (Piece::Pawn, s),
+        };
+
+        // Trailing promotion, with or without '='
+        let mut promotion = None;
+        if let Some(&last) = rest.as_bytes().last() {
+            let promo = match last {
+                b'N' => Some(Piece::Knight),
+                b'B' => Some(Piece::Bishop),
+                b'R' => Some(Piece::Rook),
+                b'Q' => Some(Piece::Queen),
+                _ => None,
+            };
+            if promo.is_some() {
+                if piece != Piece::Pawn {
+                    return Err(SanError::Malformed);
+                }
+                promotion = promo;
+                rest = &rest[..rest.len() - 1];
+                rest = rest.strip_suffix('=').unwrap_or(rest);
+            }
+        }
+
+        // Destination square is always the last two characters
+        if rest.len() < 2 {
+            return Err(SanError::Malformed);
+        }
+        let to = Square::from_algebraic(&rest[rest.len() - 2..]).ok_or(SanError::Malformed)?;
+        let mut prefix = &rest[..rest.len() - 2];
+        prefix = prefix.strip_suffix('x').unwrap_or(prefix);
+
+        // Optional file and/or rank disambiguation
+        let mut from_file: Option<File> = None;
+        let mut from_rank: Option<Rank> = None;
+        for c in prefix.chars() {
+            if let (Some(file), None, None) = (File::from_char(c), from_file, from_rank) {
+                if c.is_ascii_lowercase() {
+                    from_file = Some(file);
+                    continue;
+                }
+            }
+            match (Rank::from_char(c), from_rank) {
+                (Some(rank), None) => from_rank = Some(rank),
+                _ => return Err(SanError::Malformed),
+            }
+        }
+
+        let mut found: Option<Move> = None;
+        for m in self.generate_moves().iter() {
+            if m.to() != to
+                || m.flag() == MoveFlag::KingCastle
+                || m.flag() == MoveFlag::QueenCastle
+                || self.move_piece_kind(m) != piece
+                || m.flag().promotion_piece() != promotion
+                || from_file.is_some_and(|f| m.from().file() != f)
+                || from_rank.is_some_and(|r| m.from().rank() != r)
+            {
+                continue;
+            }
+            if found.is_some() {
+                return Err(SanError::Ambiguous);
+            }
+            found = Some(m);
+        }
+        found.ok_or(SanError::Illegal)
+    }
+
+    /// Append the file, rank, or square needed to tell `mv` apart from
+    /// other legal moves of the same piece type to the same square.
+    fn push_disambiguation(&self, san: &mut String, mv: Move, piece: Piece) {
+        let from = mv.from();
+        let mut ambiguous = false;
+        let mut same_file = false;
+        let mut same_rank = false;
+
+        for other in self.generate_moves().iter() {
+            if other.to() != mv.to() || other.from() == from
+                || self.move_piece_kind(other) != piece
+            {
+                continue;
+            }
+            ambiguous = true;
+            same_file |= other.from().file() == from.file();
+            same_rank |= other.from().rank() == from.rank();
+        }
+
+        if !ambiguous {
+            return;
+        }
+        if !same_file {
+            san.push(from.file().to_char());
+        } else if !same_rank {
+            san.push(from.rank().to_char());
+        } else {
+            san.push(from.file().to_char());
+            san.push(from.rank().to_char());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn san(fen: &str, from: Square, to: Square, flag: MoveFlag) -> String {
+        let board = Board::from_fen(fen).unwrap();
+        let mv = Move::new(from, to, flag);
+        assert!(board.generate_moves().contains(mv), "illegal test move {}", mv);
+        board.move_to_san(mv)
+    }
+
+    #[test]
+    fn test_san_basic() {
+        let start = crate::testing::STARTPOS;
+        assert_eq!(san(start, Square::G1, Square::F3, MoveFlag::Quiet), "Nf3");
+        assert_eq!(san(start, Square::E2, Square::E4, MoveFlag::DoublePawnPush), "e4");
+        assert_eq!(
+            san("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1", Square::E4, Square::D5, MoveFlag::Capture),
+            "exd5"
+        );
+        assert_eq!(
+            san("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", Square::E1, Square::G1, MoveFlag::KingCastle),
+            "O-O"
+        );
+        assert_eq!(
+            san("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", Square::E8, Square::C8, MoveFlag::QueenCastle),
+            "O-O-O"
+        );
+        assert_eq!(
+            san("4k3/8/8/3Pp3/8/8/8/4K3 w - e6 0 1", Square::D5, Square::E6, MoveFlag::EnPassant),
+            "dxe6"
+        );
+    }
+
+    #[test]
+    fn test_san_promotion_and_check() {
+        assert_eq!(
+            san("8/4P3/8/8/8/8/k7/4K3 w - - 0 1", Square::E7, Square::E8, MoveFlag::PromoQueen),
+            "e8=Q"
+        );
+        assert_eq!(
+            san("3k4/4P3/8/8/8/8/8/4K3 w - - 0 1", Square::E7, Square::E8, MoveFlag::PromoRook),
+            "e8=R+"
+        );
+        assert_eq!(
+            san("3rk3/4P3/8/8/8/8/8/4K3 w - - 0 1", Square::E7, Square::D8, MoveFlag::PromoQueenCapture),
+            "exd8=Q+"
+        );
+        // Fool's mate
+        assert_eq!(
+            san("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2",
+                Square::D8, Square::H4, MoveFlag::Quiet),
+            "Qh4#"
+        );
+    }
+
+    #[test]
+    fn test_san_disambiguation() {
+        // Knights on b1 and f3 can both reach d2
+        assert_eq!(
+            san("4k3/8/8/8/8/5N2/8/1N2K3 w - - 0 1", Square::B1, Square::D2, MoveFlag::Quiet),
+            "Nbd2"
+        );
+        // Knights on f1 and f3 share a file
+        assert_eq!(
+            san("4k3/8/8/8/8/5N2/8/4KN2 w - - 0 1", Square::F1, Square::D2, MoveFlag::Quiet),
+            "N1d2"
+        );
+        // Queens on a1, a3 and c1 all reach b2: needs the full square
+        assert_eq!(
+            san("4k3/8/8/8/8/Q7/8/Q1Q1K3 w - - 0 1", Square::A1, Square::B2, MoveFlag::Quiet),
+            "Qa1b2"
+        );
+    }
+
+    #[test]
+    fn test_parse_san() {
+        let board = Board::startpos();
+        assert_eq!(board.parse_san("Nf3"), Ok(Move::new(Square::G1, Square::F3, MoveFlag::Quiet)));
+        assert_eq!(board.parse_san("e4!?"), Ok(Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush)));
+        assert_eq!(board.parse_san("Nf6"), Err(SanError::Illegal));
+        assert_eq!(board.parse_san("Zz9"), Err(SanError::Malformed));
+        assert_eq!(board.parse_san(""), Err(SanError::Malformed));
+
+        let board = Board::from_fen("r3k2r/8/8/1n1Ppn2/8/8/4Q3/R3K2R b KQkq - 0 1").unwrap();
+        assert_eq!(board.parse_san("Nbd6"), Ok(Move::new(Square::B5, Square::D6, MoveFlag::Quiet)));
+        assert_eq!(board.parse_san("Nd6"), Err(SanError::Ambiguous));
+        assert_eq!(board.parse_san("O-O-O"), Ok(Move::new(Square::E8, Square::C8, MoveFlag::QueenCastle)));
+
+        let board = Board::from_fen("r3k2r/8/8/3Pp3/8/8/4Q3/R3K2R w KQkq e6 0 1").unwrap();
+        assert_eq!(board.parse_san("dxe6 e.p."), Ok(Move::new(Square::D5, Square::E6, MoveFlag::EnPassant)));
+        assert_eq!(board.parse_san("Qxe5+"), Ok(Move::new(Square::E2, Square::E5, MoveFlag::Capture)));
+
+        let board = Board::from_fen("3rk3/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            board.parse_san("exd8=N"),
+            Ok(Move::new(Square::E7, Square::D8, MoveFlag::PromoKnightCapture))
+        );
+        assert_eq!(board.parse_san("exd8"), Err(SanError::Illegal));
+    }
+
+    #[test]
+    fn test_san_roundtrip() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        for mv in board.generate_moves().iter() {
+            assert_eq!(board.parse_san(&board.move_to_san(mv)), Ok(mv));
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Game-over detection.
+
+use crate::board::Board;
+
+/// Classification of a position for game-loop purposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameStatus {
+    /// The game continues.
+    Ongoing,
+    /// The side to move is checkmated.
+    Checkmate,
+    /// The side to move has no legal moves but is not in check.
+    Stalemate,
+    /// One hundred plies without a capture or pawn move.
+    DrawFiftyMove,
+    /// Neither side can possibly deliver mate.
+    DrawInsufficientMaterial,
+}
+
+impl GameStatus {
+    /// Check if the game has ended.
+    #[inline(always)]
+    pub const fn is_game_over(self) -> bool {
+        !matches!(self, GameStatus::Ongoing)
+    }
+
+    /// Check if the game ended in a draw.
+    #[inline(always)]
+    pub const fn is_draw(self) -> bool {
+        matches!(
+            self,
+            GameStatus::Stalemate | GameStatus::DrawFiftyMove | GameStatus::DrawInsufficientMaterial
+        )
+    }
+}
+
+impl Board {
+    /// Classify the position: checkmate, stalemate, draw, or ongoing.
+    ///
+    /// Mate and stalemate take precedence over the fifty-move rule.
+    pub fn status(&self) -> GameStatus {
+        if !self.has_legal_moves() {
+            return if self.in_check() {
+                GameStatus::Checkmate
+            } else {
+                GameStatus::Stalemate
+            };
+        }
+        if self.halfmove_clock() >= 100 {
+            return GameStatus::DrawFiftyMove;
+        }
+        if self.has_insufficient_material() {
+            return GameStatus::DrawInsufficientMaterial;
+        }
+        GameStatus::Ongoing
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn status(fen: &str) -> GameStatus {
+        Board::from_fen(fen).unwrap().status()
+    }
+
+    #[test]
+    fn test_status_mate_and_stalemate() {
+        assert_eq!(status(crate::testing::STARTPOS), GameStatus::Ongoing);
+        // Fool's mate
+        assert_eq!(
+            status("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"),
+            GameStatus::Checkmate
+        );
+        // Queen stalemates the lone king
+        assert_eq!(status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"), GameStatus::Stalemate);
+        assert!(GameStatus::Stalemate.is_draw());
+        assert!(!GameStatus::Checkmate.is_draw());
+    }
+
+    #[test]
+    fn test_status_draws() {
+        assert_eq!(status("4k3/8/8/8/8/8/4P3/4K3 w - - 100 80"), GameStatus::DrawFiftyMove);
+        assert_eq!(status("4k3/8/8/8/8/8/8/4K3 w - - 0 1"), GameStatus::DrawInsufficientMaterial);
+        assert_eq!(status("4k3/8/8/8/8/8/8/4KN2 w - - 0 1"), GameStatus::DrawInsufficientMaterial);
+        // Bishops on c1 and f8: both dark squares
+        assert_eq!(status("5bk1/8/8/8/8/8/8/2B1K3 w - - 0 1"), GameStatus::DrawInsufficientMaterial);
+        // Bishops on c1 and c8: opposite colors
+        assert_eq!(status("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1"), GameStatus::Ongoing);
+        assert_eq!(status("4k3/8/8/8/8/8/8/3NKN2 w - - 0 1"), GameStatus::Ongoing);
+    }
+}
@@ -0,0 +1,169 @@
+//! UCI long algebraic move parsing.
+
+use super::{Move, MoveFlag};
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::types::{Piece, Square};
+
+impl Board {
+    /// Parse a UCI move such as `e2e4` or `e7e8q` in this position.
+    ///
+    /// The flag is inferred from the board, so castling, en passant and
+    /// double pushes need no special notation. In Chess960 castling is
+    /// written as the king taking its own rook (`f1h1`), as
+    /// [`Board::write_move_uci`] produces. Returns `None` if the string is
+    /// malformed or the move is not legal here.
+    pub fn parse_uci(&self, uci: &str) -> Option<Move> {
+        if !uci.is_ascii() || !(4..=5).contains(&uci.len()) {
+            return None;
+        }
+        let from = Square::from_algebraic(&uci[0..2])?;
+        let to = Square::from_algebraic(&uci[2..4])?;
+        let promo = match uci.as_bytes().get(4) {
+            None => None,
+            Some(b'n') => Some(Piece::Knight),
+            Some(b'b') => Some(Piece::Bishop),
+            Some(b'r') => Some(Piece::Rook),
+            Some(b'q') => Some(Piece::Queen),
+            Some(_) => return None,
+        };
+
+        let (piece, color) = self.piece_at(from)?;
+        if color != self.turn() {
+            return None;
+        }
+        if self.is_chess960() && piece == Piece::King && (self.us() & Bitboard::from_square(to)).any() {
+            return self.parse_castle_960(from, to);
+        }
+        let capture = (self.them() & Bitboard::from_square(to)).any();
+        let distance = (from.index() as i8 - to.index() as i8).abs();
+
+        let flag = match (piece, promo) {
+            (Piece::Pawn, Some(p)) => MoveFlag::promotion(p, capture),
+            (_, Some(_)) => return None,
+            (Piece::Pawn, None) if Some(to) == self.ep_square() => MoveFlag::EnPassant,
+            (Piece::Pawn, None) if distance == 16 => MoveFlag::DoublePawnPush,
+            (Piece::King, None) if !self.is_chess960() && to.index() == from.index() + 2 => MoveFlag::KingCastle,
+            (Piece::King, None) if !self.is_chess960() && from.index() == to.index() + 2 => MoveFlag::QueenCastle,
+            _ if capture => MoveFlag::Capture,
+            _ => MoveFlag::Quiet,
+        };
+
+        let mv = Move::new(from, to, flag);
+        self.generate_moves().contains(mv).then_some(mv)
+    }
+
+    /// Resolve a Chess960 king-takes-rook castle into the internal
+    /// king-destination encoding.
+    fn parse_castle_960(&self, from: Square, rook: Square) -> Option<Move> {
+        let kingside = rook.index() > from.index();
+        if rook != self.castle_rook_square(self.turn(), kingside) {
+            return None;
+        }
+        let (king_to, _) = self.castle_destinations(self.turn(), kingside);
+        let flag = if kingside { MoveFlag::KingCastle } else { MoveFlag::QueenCastle };
+        let mv = Move::new(from, king_to, flag);
+        self.generate_moves().contains(mv).then_some(mv)
+    }
+
+    /// Write `mv` in UCI notation as this position would send it and return
+    /// its length.
+    ///
+    /// Identical to [`Move::write_uci`] except for Chess960 castling, which
+    /// is written as the king taking its own rook so that it stays
+    /// unambiguous and survives [`Board::parse_uci`].
+    ///
+    /// # Panics
+    /// Panics if `buf` is too short; 5 bytes always suffice.
+    pub fn write_move_uci(&self, mv: Move, buf: &mut [u8]) -> usize {
+        if self.is_chess960() && mv.is_castle() {
+            let rook = self.castle_rook_square(self.turn(), mv.flag() == MoveFlag::KingCastle);
+            return Move::new(mv.from(), rook, MoveFlag::Quiet).write_uci(buf);
+        }
+        mv.write_uci(buf)
+    }
+
+    /// Convert `mv` to UCI notation for this position.
+    /// See [`Board::write_move_uci`].
+    #[cfg(feature = "std")]
+    pub fn move_to_uci(&self, mv: Move) -> String {
+        let mut buf = [0u8; 5];
+        let len = self.write_move_uci(mv, &mut buf);
+        buf[..len].iter().map(|&b| b as char).collect()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::testing::KIWIPETE;
+
+    #[test]
+    fn test_parse_uci_flags() {
+        let board = Board::startpos();
+        assert_eq!(board.parse_uci("e2e4").unwrap().flag(), MoveFlag::DoublePawnPush);
+        assert_eq!(board.parse_uci("g1f3").unwrap().flag(), MoveFlag::Quiet);
+        assert_eq!(board.parse_uci("e2e5"), None);
+        assert_eq!(board.parse_uci("e7e5"), None);
+        assert_eq!(board.parse_uci("e2e4q"), None);
+        assert_eq!(board.parse_uci("xx"), None);
+
+        let board = Board::from_fen(KIWIPETE).unwrap();
+        assert_eq!(board.parse_uci("e1g1").unwrap().flag(), MoveFlag::KingCastle);
+        assert_eq!(board.parse_uci("e1c1").unwrap().flag(), MoveFlag::QueenCastle);
+        assert_eq!(board.parse_uci("e5f7").unwrap().flag(), MoveFlag::Capture);
+
+        let board = Board::from_fen("4k3/1P6/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        assert_eq!(board.parse_uci("e5d6").unwrap().flag(), MoveFlag::EnPassant);
+        assert_eq!(board.parse_uci("b7b8n").unwrap().flag(), MoveFlag::PromoKnight);
+        assert_eq!(board.parse_uci("b7b8"), None);
+    }
+
+    #[test]
+    fn test_parse_uci_roundtrip() {
+        let board = Board::from_fen(KIWIPETE).unwrap();
+        for mv in board.generate_moves().iter() {
+            assert_eq!(board.parse_uci(&mv.to_uci()), Some(mv));
+            assert_eq!(board.move_to_uci(mv), mv.to_uci());
+        }
+    }
+
+    #[test]
+    fn test_parse_uci_chess960_castling() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R4KR1 w GA - 0 1").unwrap();
+        let short = board.parse_uci("f1g1").unwrap();
+        assert_eq!(short, Move::new(Square::F1, Square::G1, MoveFlag::KingCastle));
+        assert_eq!(board.move_to_uci(short), "f1g1");
+        let long = board.parse_uci("f1a1").unwrap();
+        assert_eq!(long, Move::new(Square::F1, Square::C1, MoveFlag::QueenCastle));
+        assert_eq!(board.move_to_uci(long), "f1a1");
+        // Standard notation does not mean castling in Chess960
+        assert_eq!(board.parse_uci("f1c1"), None);
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/1RK4R w HB - 0 1").unwrap();
+        let long = board.parse_uci("c1b1").unwrap();
+        assert_eq!(long, Move::new(Square::C1, Square::C1, MoveFlag::QueenCastle));
+        assert_eq!(board.move_to_uci(long), "c1b1");
+        assert_eq!(board.move_to_uci(board.parse_uci("c1h1").unwrap()), "c1h1");
+    }
+
+    #[test]
+    fn test_parse_uci_chess960_roundtrip() {
+        let mut castles = 0;
+        for fen in [
+            "4k3/8/8/8/8/8/8/R4KR1 w GA - 0 1",
+            "4k3/8/8/8/8/8/8/1RK4R w HB - 0 1",
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+            "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            assert!(board.is_chess960());
+            let moves = board.generate_moves();
+            castles += moves.iter().filter(|m| m.is_castle()).count();
+            for mv in moves.iter() {
+                assert_eq!(board.parse_uci(&board.move_to_uci(mv)), Some(mv), "{} {:?}", fen, mv);
+            }
+        }
+        assert!(castles >= 4);
+    }
+}
@@ -0,0 +1,150 @@
+//! Extended 32-bit move encoding for variants with piece drops.
+//!
+//! The core [`Move`] stays 16 bits; this type wraps it and adds a drop form
+//! for crazyhouse-style engines.
+
+use core::fmt;
+use super::Move;
+use crate::types::{Square, Piece};
+
+/// A move that is either a regular board move or a piece drop.
+///
+/// Layout:
+/// - Bits 0-15: Regular [`Move`] (unused for drops, except bits 6-11)
+/// - Bits 6-11: Drop destination square (0-63)
+/// - Bit 16: Drop flag
+/// - Bits 17-19: Dropped piece type (0-5)
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct VariantMove(u32);
+
+impl VariantMove {
+    const DROP_FLAG: u32 = 1 << 16;
+
+    /// Wrap a regular move.
+    #[inline(always)]
+    pub const fn from_move(mv: Move) -> VariantMove {
+        VariantMove(mv.bits() as u32)
+    }
+
+    /// Create a drop of `piece` onto `to`.
+    #[inline(always)]
+    pub const fn new_drop(piece: Piece, to: Square) -> VariantMove {
+        VariantMove(
+            ((to.index() as u32) << 6)
+                | Self::DROP_FLAG
+                | ((piece as u32) << 17)
+        )
+    }
+
+    /// Check if this is a drop.
+    #[inline(always)]
+    pub const fn is_drop(self) -> bool {
+        (self.0 & Self::DROP_FLAG) != 0
+    }
+
+    /// Get the regular move, if this is not a drop.
+    #[inline(always)]
+    pub const fn as_move(self) -> Option<Move> {
+        if self.is_drop() {
+            None
+        } else {
+            Some(Move::from_bits(self.0 as u16))
+        }
+    }
+
+    /// Get the dropped piece, if this is a drop.
+    #[inline(always)]
+    pub const fn drop_piece(self) -> Option<Piece> {
+        if self.is_drop() {
+            Piece::from_index(((self.0 >> 17) & 7) as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Get the destination square.
+    #[inline(always)]
+    pub const fn to(self) -> Square {
+        unsafe { Square::from_index_unchecked(((self.0 >> 6) & 0x3F) as u8) }
+    }
+
+    /// Get raw bits.
+    #[inline(always)]
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Parse a drop in UCI notation (e.g., "P@e4").
+    /// Regular moves need a board to recover their flags and are not handled here.
+    pub fn from_uci_drop(s: &str) -> Option<VariantMove> {
+        let (piece, sq) = s.split_once('@')?;
+        let mut chars = piece.chars();
+        let (piece, _) = Piece::from_char(chars.next()?.to_ascii_uppercase())?;
+        if chars.next().is_some() || piece == Piece::King {
+            return None;
+        }
+        Some(VariantMove::new_drop(piece, Square::from_algebraic(sq)?))
+    }
+
+    /// Convert to UCI string.
+    #[cfg(feature = "std")]
+    pub fn to_uci(self) -> String {
+        self.to_string()
+    }
+}
+
+impl From<Move> for VariantMove {
+    #[inline(always)]
+    fn from(mv: Move) -> VariantMove {
+        VariantMove::from_move(mv)
+    }
+}
+
+impl fmt::Debug for VariantMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VariantMove({})", self)
+    }
+}
+
+impl fmt::Display for VariantMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.drop_piece() {
+            Some(piece) => write!(f, "{}@{}", piece, self.to()),
+            None => write!(f, "{}", Move::from_bits(self.0 as u16)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::types::Color;
+    use crate::movegen::MoveFlag;
+
+    #[test]
+    fn test_drop_encoding() {
+        let drop = VariantMove::from_uci_drop("P@e4").unwrap();
+        assert!(drop.is_drop());
+        assert_eq!(drop.drop_piece(), Some(Piece::Pawn));
+        assert_eq!(drop.to(), Square::E4);
+        assert_eq!(drop.as_move(), None);
+        assert_eq!(drop.to_uci(), "P@e4");
+        assert_eq!(VariantMove::from_uci_drop("K@e4"), None);
+
+        let mv = Move::new(Square::E2, Square::E4, MoveFlag::DoublePawnPush);
+        let wrapped = VariantMove::from(mv);
+        assert!(!wrapped.is_drop());
+        assert_eq!(wrapped.as_move(), Some(mv));
+    }
+
+    #[test]
+    fn test_drop_piece() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        board.drop_piece(Piece::Knight, Square::E4);
+        assert_eq!(board.piece_at(Square::E4), Some((Piece::Knight, Color::White)));
+        assert_eq!(board.turn(), Color::Black);
+        assert_eq!(board.hash(), Board::from_fen("4k3/8/8/8/4N3/8/8/4K3 b - - 0 1").unwrap().hash());
+    }
+}
@@ -0,0 +1,145 @@
+//! Serde support (only available with the serde feature).
+//!
+//! `Piece`, `Color` and `Bitboard` derive their impls. The types below
+//! have invariants a derive would not enforce, so they go through their
+//! text forms instead: boards as FEN, squares as `e4`, and castling rights
+//! as `KQkq`.
+//!
+//! Moves are the exception. UCI text drops the move flag (a capture and a
+//! quiet move look the same), so they are stored as their raw 16-bit
+//! encoding, which round-trips exactly.
+
+use core::fmt;
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::board::Board;
+use crate::movegen::Move;
+use crate::types::{CastleRights, Square};
+
+/// Visitor that parses a string with `parse`, naming `expecting` on failure.
+struct TextVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&str) -> Option<T>,
+}
+
+impl<T> Visitor<'_> for TextVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        (self.parse)(v).ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
+
+macro_rules! text_serde {
+    ($ty:ty, $expecting:literal, $parse:expr) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(&Text(self))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$ty, D::Error> {
+                deserializer.deserialize_str(TextVisitor { expecting: $expecting, parse: $parse })
+            }
+        }
+    };
+}
+
+/// Text form used for serialization.
+struct Text<'a, T>(&'a T);
+
+impl fmt::Display for Text<'_, Board> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_fen())
+    }
+}
+
+impl fmt::Display for Text<'_, Square> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
+    }
+}
+
+impl fmt::Display for Text<'_, CastleRights> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.to_fen())
+    }
+}
+
+text_serde!(Board, "a FEN string", |s| Board::from_fen(s).ok());
+text_serde!(Square, "a square such as e4", Square::from_algebraic);
+text_serde!(CastleRights, "castling rights such as KQkq", CastleRights::from_fen);
+
+impl Serialize for Move {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.bits())
+    }
+}
+
+impl<'de> Deserialize<'de> for Move {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Move, D::Error> {
+        let bits = u16::deserialize(deserializer)?;
+        // Flag nibbles 6 and 7 are unassigned
+        if matches!(bits >> 12, 6 | 7) {
+            return Err(de::Error::invalid_value(de::Unexpected::Unsigned(bits as u64), &"a move encoding"));
+        }
+        Ok(Move::from_bits(bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboard::Bitboard;
+    use crate::movegen::MoveFlag;
+    use crate::types::{Color, Piece};
+
+    fn roundtrip<T: Serialize + for<'de> Deserialize<'de>>(value: &T) -> (String, T) {
+        let json = serde_json::to_string(value).unwrap();
+        let back = serde_json::from_str(&json).unwrap();
+        (json, back)
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        let (json, back) = roundtrip(&board);
+        assert_eq!(json, format!("\"{}\"", crate::testing::KIWIPETE));
+        assert_eq!(back.to_fen(), board.to_fen());
+
+        let mv = Move::new(Square::E7, Square::E8, MoveFlag::PromoQueen);
+        assert_eq!(roundtrip(&mv), (mv.bits().to_string(), mv));
+        assert_eq!(roundtrip(&Square::E4), ("\"e4\"".to_string(), Square::E4));
+        assert_eq!(roundtrip(&CastleRights::ALL), ("\"KQkq\"".to_string(), CastleRights::ALL));
+        assert_eq!(roundtrip(&Piece::Knight).1, Piece::Knight);
+        assert_eq!(roundtrip(&Color::Black).1, Color::Black);
+        assert_eq!(roundtrip(&Bitboard::RANK_4).1, Bitboard::RANK_4);
+    }
+
+    #[test]
+    fn test_serde_move_keeps_flag() {
+        for fen in [crate::testing::KIWIPETE, "4k3/1P6/8/3pP3/8/8/8/R3K3 w Q d6 0 1"] {
+            let board = Board::from_fen(fen).unwrap();
+            for mv in board.generate_moves().iter() {
+                let back = roundtrip(&mv).1;
+                assert_eq!(back, mv);
+                assert_eq!(board.make_move_new(back), board.make_move_new(mv));
+            }
+        }
+    }
+
+    #[test]
+    fn test_serde_rejects_bad_text() {
+        assert!(serde_json::from_str::<Board>("\"not a fen\"").is_err());
+        assert!(serde_json::from_str::<Square>("\"z9\"").is_err());
+        assert!(serde_json::from_str::<CastleRights>("\"KX\"").is_err());
+        assert!(serde_json::from_str::<Move>("\"e2e4\"").is_err());
+        assert!(serde_json::from_str::<Move>(&0x6000u16.to_string()).is_err());
+    }
+}
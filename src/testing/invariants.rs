@@ -0,0 +1,63 @@
+//! Assertions for board invariants across make and unmake.
+
+use crate::board::Board;
+
+/// Assert that making and unmaking every legal move restores `board` exactly.
+///
+/// For each move this checks that [`Board::make_move`] agrees with
+/// [`Board::make_move_new`], that the incremental hashes match a full
+/// recompute, and that [`Board::unmake_move`] brings back the FEN (move
+/// counters included), hashes, checkers and pinned pieces.
+///
+/// # Panics
+/// Panics with the offending FEN and move on the first mismatch.
+pub fn assert_make_unmake_consistent(board: &Board) {
+    let fen = board.to_fen();
+    for mv in board.generate_moves().iter() {
+        let mut copy = *board;
+        let undo = copy.make_move(mv);
+        let made = board.make_move_new(mv);
+        assert_eq!(copy.to_fen(), made.to_fen(), "make_move vs make_move_new: {fen} {mv}");
+        assert_eq!(copy.hash(), made.hash(), "make_move vs make_move_new hash: {fen} {mv}");
+        assert_eq!(copy.pawn_hash(), made.pawn_hash(), "make_move vs make_move_new pawn hash: {fen} {mv}");
+        assert_eq!(copy.checkers(), made.checkers(), "make_move vs make_move_new checkers: {fen} {mv}");
+        assert_eq!(copy.pinned(), made.pinned(), "make_move vs make_move_new pinned: {fen} {mv}");
+        assert!(copy.hash_is_valid(), "incremental hash drifted: {fen} {mv}");
+
+        copy.unmake_move(mv, undo);
+        assert_eq!(copy.to_fen(), fen, "unmake did not restore the position: {mv}");
+        assert_eq!(copy.hash(), board.hash(), "unmake did not restore the hash: {fen} {mv}");
+        assert_eq!(copy.pawn_hash(), board.pawn_hash(), "unmake did not restore the pawn hash: {fen} {mv}");
+        assert_eq!(copy.checkers(), board.checkers(), "unmake did not restore checkers: {fen} {mv}");
+        assert_eq!(copy.pinned(), board.pinned(), "unmake did not restore pinned: {fen} {mv}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{KIWIPETE, POSITION_3, POSITION_4, POSITION_5, POSITION_6, STARTPOS};
+
+    #[test]
+    fn test_standard_positions_consistent() {
+        for fen in [STARTPOS, KIWIPETE, POSITION_3, POSITION_4, POSITION_5, POSITION_6] {
+            assert_make_unmake_consistent(&Board::from_fen(fen).unwrap());
+        }
+        // Chess960 castling with the king landing on its rook's square
+        let board = Board::from_fen("1r2k1r1/8/8/8/8/8/8/1R2K1R1 w GBgb - 0 1").unwrap();
+        assert_make_unmake_consistent(&board);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_random_positions_consistent() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..200 {
+            let board = crate::testing::random_legal_position(&mut rng, 120);
+            assert_make_unmake_consistent(&board);
+        }
+    }
+}
@@ -2,6 +2,17 @@
 
 mod perft;
 mod positions;
+mod setup;
+mod invariants;
+#[cfg(feature = "rand")]
+mod random;
 
-pub use perft::{perft, perft_divide};
+pub use perft::{divide_to, perft, perft_detailed, perft_divide, perft_divide_moves, perft_hash_check, perft_tt, perft_unmake, perft_with, run_perft_suite, PerftFailure, PerftStats, PerftTable};
 pub use positions::*;
+pub use setup::place_pieces;
+pub use invariants::assert_make_unmake_consistent;
+#[cfg(feature = "rand")]
+pub use random::random_legal_position;
+
+#[cfg(feature = "parallel")]
+pub use perft::perft_parallel;
@@ -1,6 +1,9 @@
 //! Perft (performance test) function for move generator validation.
 
+use core::fmt;
 use crate::board::Board;
+use crate::movegen::{Move, MoveFlag};
+use super::PERFT_RESULTS;
 
 /// Run perft to a given depth.
 /// Returns the number of leaf nodes at the given depth.
@@ -25,20 +28,327 @@ pub fn perft(board: &Board, depth: u32) -> u64 {
     nodes
 }
 
+/// Run perft with the root moves split across threads.
+/// Each worker owns its board copies, so only the totals are shared.
+#[cfg(feature = "parallel")]
+pub fn perft_parallel(board: &Board, depth: u32) -> u64 {
+    if depth <= 1 {
+        return perft(board, depth);
+    }
+
+    let moves = board.generate_moves();
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = moves.len().div_ceil(threads).max(1);
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = moves
+            .as_slice()
+            .chunks(chunk)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|&mv| perft(&board.make_move_new(mv), depth - 1))
+                        .sum::<u64>()
+                })
+            })
+            .collect();
+        workers.into_iter().map(|w| w.join().unwrap()).sum()
+    })
+}
+
+/// Run perft using make/unmake on a single board instead of copying
+/// it at every node. Only the state needed for generation is kept up
+/// to date, so the hash and counters are stale until unmade.
+pub fn perft_unmake(board: &mut Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    if depth == 1 {
+        return board.generate_moves_count();
+    }
+
+    let moves = board.generate_moves();
+    let mut nodes = 0u64;
+
+    for mv in &moves {
+        let undo = board.make_move_fast(&mv);
+        board.update_checkers();
+        nodes += perft_unmake(board, depth - 1);
+        board.unmake_move_fast(&mv, undo);
+    }
+
+    nodes
+}
+
+/// Run perft, calling `f` with the parent board and move for every leaf.
+/// Bulk counting is skipped so each leaf move is visited individually.
+/// At depth 0 there is no leaf move, so `f` is never called.
+pub fn perft_with<F: FnMut(&Board, Move)>(board: &Board, depth: u32, mut f: F) -> u64 {
+    perft_with_impl(board, depth, &mut f)
+}
+
+fn perft_with_impl<F: FnMut(&Board, Move)>(board: &Board, depth: u32, f: &mut F) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves = board.generate_moves();
+
+    if depth == 1 {
+        for mv in &moves {
+            f(board, mv);
+        }
+        return moves.len() as u64;
+    }
+
+    let mut nodes = 0u64;
+
+    for mv in &moves {
+        let new_board = board.make_move_new(mv);
+        nodes += perft_with_impl(&new_board, depth - 1, f);
+    }
+
+    nodes
+}
+
+/// Run perft using make/unmake, asserting at every node that the
+/// incrementally maintained hash matches a full recompute.
+///
+/// # Panics
+/// Panics on the first hash mismatch, naming the position and move.
+pub fn perft_hash_check(board: &mut Board, depth: u32) -> u64 {
+    assert_eq!(board.hash(), board.recompute_hash(), "hash mismatch at {}", board.to_fen());
+
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves = board.generate_moves();
+    let mut nodes = 0u64;
+
+    for mv in &moves {
+        let before = board.hash();
+        let undo = board.make_move(mv);
+        assert_eq!(
+            board.hash(),
+            board.recompute_hash(),
+            "hash mismatch after {} at {}",
+            mv,
+            board.to_fen()
+        );
+        nodes += perft_hash_check(board, depth - 1);
+        board.unmake_move(mv, undo);
+        assert_eq!(board.hash(), before, "hash not restored after unmaking {}", mv);
+    }
+
+    nodes
+}
+
+/// Always-replace hash table of perft subtree counts.
+pub struct PerftTable {
+    entries: Vec<PerftEntry>,
+    mask: usize,
+}
+
+#[derive(Clone, Copy, Default)]
+struct PerftEntry {
+    hash: u64,
+    depth: u32,
+    nodes: u64,
+}
+
+impl PerftTable {
+    /// Create a table using at most `mb` megabytes (at least one entry).
+    /// The entry count is rounded down to a power of two.
+    pub fn with_capacity_mb(mb: usize) -> PerftTable {
+        let bytes = mb.saturating_mul(1024 * 1024);
+        let count = (bytes / core::mem::size_of::<PerftEntry>()).max(1);
+        let count = 1usize << count.ilog2();
+        PerftTable {
+            entries: vec![PerftEntry::default(); count],
+            mask: count - 1,
+        }
+    }
+
+    /// Number of slots in the table.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Forget all stored counts.
+    pub fn clear(&mut self) {
+        self.entries.fill(PerftEntry::default());
+    }
+
+    #[inline]
+    fn probe(&self, hash: u64, depth: u32) -> Option<u64> {
+        let entry = &self.entries[hash as usize & self.mask];
+        // depth 0 is never stored, so an empty slot cannot match
+        (entry.hash == hash && entry.depth == depth).then_some(entry.nodes)
+    }
+
+    #[inline]
+    fn store(&mut self, hash: u64, depth: u32, nodes: u64) {
+        self.entries[hash as usize & self.mask] = PerftEntry { hash, depth, nodes };
+    }
+}
+
+/// Run perft, caching subtree counts by `(hash, depth)` in `tt`.
+/// Relies on the Zobrist hash being collision-free in practice.
+pub fn perft_tt(board: &Board, depth: u32, tt: &mut PerftTable) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    if depth == 1 {
+        return board.generate_moves_count();
+    }
+
+    if let Some(nodes) = tt.probe(board.hash(), depth) {
+        return nodes;
+    }
+
+    let moves = board.generate_moves();
+    let mut nodes = 0u64;
+
+    for mv in &moves {
+        let new_board = board.make_move_new(mv);
+        nodes += perft_tt(&new_board, depth - 1, tt);
+    }
+
+    tt.store(board.hash(), depth, nodes);
+    nodes
+}
+
+/// Leaf move counts by category, as in the published perft tables.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PerftStats {
+    pub nodes: u64,
+    pub captures: u64,
+    pub en_passant: u64,
+    pub castles: u64,
+    pub promotions: u64,
+    pub checks: u64,
+    pub checkmates: u64,
+}
+
+/// Run perft, classifying every leaf move.
+/// Much slower than `perft` since each leaf is made and tested for mate.
+pub fn perft_detailed(board: &Board, depth: u32) -> PerftStats {
+    let mut stats = PerftStats::default();
+    if depth == 0 {
+        stats.nodes = 1;
+    } else {
+        perft_detailed_impl(board, depth, &mut stats);
+    }
+    stats
+}
+
+fn perft_detailed_impl(board: &Board, depth: u32, stats: &mut PerftStats) {
+    let moves = board.generate_moves();
+
+    for mv in &moves {
+        let new_board = board.make_move_new(mv);
+        if depth > 1 {
+            perft_detailed_impl(&new_board, depth - 1, stats);
+            continue;
+        }
+
+        let flag = mv.flag();
+        stats.nodes += 1;
+        stats.captures += flag.is_capture() as u64;
+        stats.en_passant += (flag == MoveFlag::EnPassant) as u64;
+        stats.castles += matches!(flag, MoveFlag::KingCastle | MoveFlag::QueenCastle) as u64;
+        stats.promotions += flag.is_promotion() as u64;
+        if new_board.in_check() {
+            stats.checks += 1;
+            stats.checkmates += !new_board.has_legal_moves() as u64;
+        }
+    }
+}
+
 /// Run perft with divide (shows count per root move).
 pub fn perft_divide(board: &Board, depth: u32) -> Vec<(String, u64)> {
+    perft_divide_moves(board, depth)
+        .into_iter()
+        .map(|(mv, count)| (board.move_to_uci(mv), count))
+        .collect()
+}
+
+/// Like [`perft_divide`], keyed on the moves themselves so callers can
+/// drill into a subtree without re-parsing UCI.
+pub fn perft_divide_moves(board: &Board, depth: u32) -> Vec<(Move, u64)> {
     let moves = board.generate_moves();
-    let mut results = Vec::new();
+    let mut results = Vec::with_capacity(moves.len());
 
     for mv in &moves {
         let new_board = board.make_move_new(mv);
-        let count = perft(&new_board, depth - 1);
-        results.push((mv.to_uci(), count));
+        let count = perft(&new_board, depth.saturating_sub(1));
+        results.push((mv, count));
     }
 
     results
 }
 
+/// Write the divide tree of `board` to `out` and return the total node count.
+///
+/// Every move down to `depth - 1` plies is listed with its subtree count,
+/// indented two spaces per ply, so the output grows like perft itself and
+/// is meant for shallow depths when hunting a movegen bug.
+pub fn divide_to<W: std::io::Write>(board: &Board, depth: u32, out: &mut W) -> std::io::Result<u64> {
+    divide_to_impl(board, depth, 0, out)
+}
+
+fn divide_to_impl<W: std::io::Write>(board: &Board, depth: u32, ply: usize, out: &mut W) -> std::io::Result<u64> {
+    let mut total = 0;
+    for (mv, count) in perft_divide_moves(board, depth) {
+        writeln!(out, "{:indent$}{}: {}", "", board.move_to_uci(mv), count, indent = 2 * ply)?;
+        if depth > 1 {
+            divide_to_impl(&board.make_move_new(mv), depth - 1, ply + 1, out)?;
+        }
+        total += count;
+    }
+    Ok(total)
+}
+
+/// A [`PERFT_RESULTS`] entry whose node count did not match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerftFailure {
+    pub fen: &'static str,
+    pub depth: u32,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+impl fmt::Display for PerftFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} depth {}: expected {}, got {}", self.fen, self.depth, self.expected, self.actual)
+    }
+}
+
+/// Run `perft` on every entry of [`PERFT_RESULTS`] and collect the mismatches.
+///
+/// Takes a few seconds in release builds; downstream crates can call it
+/// from their own test suites.
+pub fn run_perft_suite() -> Result<(), Vec<PerftFailure>> {
+    let failures: Vec<PerftFailure> = PERFT_RESULTS
+        .iter()
+        .filter_map(|result| {
+            let board = Board::from_fen(result.fen).expect("PERFT_RESULTS holds valid FENs");
+            let actual = perft(&board, result.depth);
+            (actual != result.nodes).then_some(PerftFailure {
+                fen: result.fen,
+                depth: result.depth,
+                expected: result.nodes,
+                actual,
+            })
+        })
+        .collect();
+    if failures.is_empty() { Ok(()) } else { Err(failures) }
+}
 
 #[cfg(test)]
 mod tests {
@@ -85,6 +395,106 @@ mod tests {
         // g2g3: 9345, g2g4: 9328, h2h3: 8457, h2h4: 9329
     }
 
+    #[test]
+    fn test_perft_divide_moves() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        let divide = perft_divide_moves(&board, 3);
+        assert_eq!(divide.len(), 48);
+        assert_eq!(divide.iter().map(|(_, c)| c).sum::<u64>(), 97862);
+        for &(mv, count) in divide.iter().take(5) {
+            assert_eq!(perft(&board.make_move_new(mv), 2), count);
+        }
+    }
+
+    #[test]
+    fn test_divide_to() {
+        let mut out = Vec::new();
+        let total = divide_to(&Board::startpos(), 2, &mut out).unwrap();
+        assert_eq!(total, 400);
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 20 + 400);
+        assert!(text.starts_with("a2a3: 20\n  a7a6: 1\n"));
+    }
+
+    #[test]
+    fn test_perft_with_callback_count() {
+        let board = Board::startpos();
+        let mut calls = 0u64;
+        let nodes = perft_with(&board, 3, |_, _| calls += 1);
+        assert_eq!(nodes, perft(&board, 3));
+        assert_eq!(calls, nodes);
+    }
+
+    #[test]
+    fn test_perft_hash_check_kiwipete() {
+        let mut board = Board::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+        ).unwrap();
+        assert_eq!(perft_hash_check(&mut board, 4), 4085603);
+    }
+
+    #[test]
+    fn test_perft_detailed() {
+        let stats = perft_detailed(&Board::startpos(), 3);
+        assert_eq!(stats, PerftStats { nodes: 8902, captures: 34, checks: 12, ..Default::default() });
+
+        let board = Board::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+        ).unwrap();
+        let stats = perft_detailed(&board, 1);
+        assert_eq!(stats, PerftStats { nodes: 48, captures: 8, castles: 2, ..Default::default() });
+        let stats = perft_detailed(&board, 2);
+        assert_eq!(
+            stats,
+            PerftStats { nodes: 2039, captures: 351, en_passant: 1, castles: 91, checks: 3, ..Default::default() }
+        );
+    }
+
+    #[test]
+    fn test_perft_tt() {
+        let mut tt = PerftTable::with_capacity_mb(1);
+        assert!(tt.capacity().is_power_of_two());
+        let board = Board::startpos();
+        assert_eq!(perft_tt(&board, 4, &mut tt), 197281);
+        // Second run is served from the table
+        assert_eq!(perft_tt(&board, 4, &mut tt), 197281);
+
+        let board = Board::from_fen(crate::testing::POSITION_3).unwrap();
+        tt.clear();
+        assert_eq!(perft_tt(&board, 4, &mut tt), perft(&board, 4));
+        assert_eq!(PerftTable::with_capacity_mb(0).capacity(), 1);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_perft_parallel() {
+        let board = Board::from_fen(crate::testing::KIWIPETE).unwrap();
+        assert_eq!(perft_parallel(&board, 3), perft(&board, 3));
+        assert_eq!(perft_parallel(&Board::startpos(), 1), 20);
+    }
+
+    #[test]
+    fn test_perft_unmake_matches_results() {
+        use crate::testing::PERFT_RESULTS;
+        for result in PERFT_RESULTS {
+            let mut board = Board::from_fen(result.fen).unwrap();
+            let fen = board.to_fen();
+            assert_eq!(perft_unmake(&mut board, result.depth), result.nodes, "{} depth {}", result.fen, result.depth);
+            assert_eq!(board.to_fen(), fen);
+        }
+    }
+
+    #[test]
+    #[ignore] // Slow test
+    fn test_perft_suite() {
+        if let Err(failures) = run_perft_suite() {
+            for failure in &failures {
+                eprintln!("{failure}");
+            }
+            panic!("{} perft mismatches", failures.len());
+        }
+    }
+
     #[test]
     #[ignore] // Slow test
     fn test_perft_startpos_depth5() {
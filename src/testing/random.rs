@@ -0,0 +1,46 @@
+//! Random reachable positions for property tests.
+
+use rand_core::RngCore;
+use crate::board::Board;
+
+/// Play random legal moves from the starting position until there are none
+/// left or `max_plies` have been played, and return the final board.
+///
+/// The same seeded `rng` always yields the same position.
+pub fn random_legal_position(rng: &mut impl RngCore, max_plies: usize) -> Board {
+    let mut board = Board::startpos();
+    for _ in 0..max_plies {
+        match board.random_move(rng) {
+            Some(mv) => board = board.make_move_new(mv),
+            None => break,
+        }
+    }
+    board
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_random_positions_deterministic() {
+        let a = random_legal_position(&mut StdRng::seed_from_u64(7), 40);
+        let b = random_legal_position(&mut StdRng::seed_from_u64(7), 40);
+        assert_eq!(a, b);
+        assert_eq!(random_legal_position(&mut StdRng::seed_from_u64(7), 0), Board::startpos());
+    }
+
+    #[test]
+    fn test_random_positions_invariants() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..200 {
+            let board = random_legal_position(&mut rng, 120);
+            assert!(board.hash_is_valid(), "{board:?}");
+            assert!(board.fen_roundtrip_ok(), "{board:?}");
+            assert_eq!(board.mirror().mirror(), board);
+            assert_eq!(board.mirror().generate_moves_count(), board.generate_moves_count());
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Build test positions from piece lists instead of FEN strings.
+
+use crate::bitboard::Bitboard;
+use crate::board::{Board, BoardBuilder, FenError};
+use crate::types::{Square, Piece, Color};
+
+/// Build a board with the given pieces and side to move.
+///
+/// Castling rights and en passant are left empty. Fails if a square is used
+/// twice or [`BoardBuilder::build`] rejects the position: either side does
+/// not have exactly one king, a pawn stands on the first or last rank, or
+/// the side not to move is in check.
+pub fn place_pieces(spec: &[(Square, Piece, Color)], turn: Color) -> Result<Board, FenError> {
+    let mut used = Bitboard::EMPTY;
+    let mut builder = BoardBuilder::new();
+    for &(sq, piece, color) in spec {
+        if used.contains(sq) {
+            return Err(FenError::DuplicateSquare(sq));
+        }
+        used |= Bitboard::from_square(sq);
+        builder = builder.put(sq, piece, color);
+    }
+    builder.side_to_move(turn).build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::perft;
+
+    #[test]
+    fn test_place_king_pawn_vs_king() {
+        let board = place_pieces(&[
+            (Square::E1, Piece::King, Color::White),
+            (Square::E2, Piece::Pawn, Color::White),
+            (Square::E8, Piece::King, Color::Black),
+        ], Color::White).unwrap();
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+        // Ke1: d1 d2 f1 f2, pawn: e3 e4; black king always has 5 replies
+        assert_eq!(perft(&board, 1), 6);
+        assert_eq!(perft(&board, 2), 30);
+    }
+
+    #[test]
+    fn test_place_pieces_rejects_illegal() {
+        // Missing black king
+        assert_eq!(
+            place_pieces(&[(Square::E1, Piece::King, Color::White)], Color::White).unwrap_err(),
+            FenError::BadKingCount
+        );
+        // Black to move while white is in check
+        assert_eq!(place_pieces(&[
+            (Square::E1, Piece::King, Color::White),
+            (Square::E8, Piece::King, Color::Black),
+            (Square::E5, Piece::Rook, Color::Black),
+        ], Color::Black).unwrap_err(), FenError::OpponentInCheck);
+        // The same square twice
+        assert_eq!(place_pieces(&[
+            (Square::E1, Piece::King, Color::White),
+            (Square::E8, Piece::King, Color::Black),
+            (Square::E1, Piece::Queen, Color::White),
+        ], Color::White).unwrap_err(), FenError::DuplicateSquare(Square::E1));
+    }
+}
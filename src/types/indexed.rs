@@ -0,0 +1,134 @@
+//! Arrays indexed by chess enums.
+
+use core::ops::{Index, IndexMut};
+use super::{Color, Piece};
+
+/// Per-color storage: slot 0 is white, slot 1 is black.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct ByColor<T>(pub [T; 2]);
+
+impl<T> ByColor<T> {
+    /// Create from white and black values.
+    #[inline(always)]
+    pub const fn new(white: T, black: T) -> ByColor<T> {
+        ByColor([white, black])
+    }
+
+    /// Get a reference to the value for a color.
+    #[inline(always)]
+    pub const fn get(&self, color: Color) -> &T {
+        &self.0[color.index()]
+    }
+
+    /// Get a mutable reference to the value for a color.
+    #[inline(always)]
+    pub fn get_mut(&mut self, color: Color) -> &mut T {
+        &mut self.0[color.index()]
+    }
+
+    /// Apply `f` to both values.
+    #[inline]
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> ByColor<U> {
+        ByColor(self.0.map(f))
+    }
+}
+
+impl<T> Index<Color> for ByColor<T> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, color: Color) -> &T {
+        &self.0[color.index()]
+    }
+}
+
+impl<T> IndexMut<Color> for ByColor<T> {
+    #[inline(always)]
+    fn index_mut(&mut self, color: Color) -> &mut T {
+        &mut self.0[color.index()]
+    }
+}
+
+/// Per-piece-type storage, one slot per [`Piece`] in index order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
+pub struct ByPiece<T>(pub [T; Piece::COUNT]);
+
+impl<T> ByPiece<T> {
+    /// Create from an array in piece index order (P, N, B, R, Q, K).
+    #[inline(always)]
+    pub const fn new(values: [T; Piece::COUNT]) -> ByPiece<T> {
+        ByPiece(values)
+    }
+
+    /// Get a reference to the value for a piece.
+    #[inline(always)]
+    pub const fn get(&self, piece: Piece) -> &T {
+        &self.0[piece.index()]
+    }
+
+    /// Get a mutable reference to the value for a piece.
+    #[inline(always)]
+    pub fn get_mut(&mut self, piece: Piece) -> &mut T {
+        &mut self.0[piece.index()]
+    }
+
+    /// Apply `f` to every value.
+    #[inline]
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> ByPiece<U> {
+        ByPiece(self.0.map(f))
+    }
+
+    /// Iterate over `(Piece, &T)` pairs in piece index order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Piece, &T)> + '_ {
+        Piece::ALL.into_iter().zip(self.0.iter())
+    }
+}
+
+impl<T> Index<Piece> for ByPiece<T> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, piece: Piece) -> &T {
+        &self.0[piece.index()]
+    }
+}
+
+impl<T> IndexMut<Piece> for ByPiece<T> {
+    #[inline(always)]
+    fn index_mut(&mut self, piece: Piece) -> &mut T {
+        &mut self.0[piece.index()]
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_color_index() {
+        let mut values = ByColor::new(10, 20);
+        assert_eq!(values[Color::White], 10);
+        assert_eq!(values[Color::Black], 20);
+
+        *values.get_mut(Color::Black) += 1;
+        assert_eq!(*values.get(Color::Black), 21);
+        assert_eq!(values.map(|v| v * 2), ByColor::new(20, 42));
+    }
+
+    #[test]
+    fn test_by_piece_index() {
+        use crate::bitboard::Bitboard;
+        use crate::types::Square;
+
+        let mut bbs = ByPiece::<Bitboard>::default();
+        bbs[Piece::Queen] = Bitboard::from_square(Square::D1);
+        assert!(bbs[Piece::Queen].contains(Square::D1));
+        assert!(bbs[Piece::King].is_empty());
+
+        let pieces: Vec<Piece> = bbs.iter().filter(|(_, bb)| bb.any()).map(|(p, _)| p).collect();
+        assert_eq!(pieces, vec![Piece::Queen]);
+    }
+}
@@ -4,8 +4,10 @@ mod square;
 mod piece;
 mod castling;
 mod direction;
+mod indexed;
 
-pub use square::{Square, File, Rank};
-pub use piece::{Piece, Color};
+pub use square::{Square, File, Rank, ParseSquareError};
+pub use piece::{Piece, Color, PIECE_VALUES, PHASE_WEIGHTS};
 pub use castling::CastleRights;
 pub use direction::Direction;
+pub use indexed::{ByColor, ByPiece};
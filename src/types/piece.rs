@@ -5,6 +5,7 @@ use core::ops::Not;
 
 /// Represents a player color.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum Color {
     White = 0,
@@ -94,6 +95,7 @@ impl fmt::Display for Color {
 
 /// Represents a piece type (without color).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum Piece {
     Pawn = 0,
@@ -104,6 +106,13 @@ pub enum Piece {
     King = 5,
 }
 
+/// Material values in centipawns, indexed by [`Piece::index`]. The king is 0.
+pub const PIECE_VALUES: [i32; Piece::COUNT] = [100, 300, 300, 500, 900, 0];
+
+/// Game-phase weights for tapered evaluation, indexed by [`Piece::index`].
+/// The starting position sums to 24.
+pub const PHASE_WEIGHTS: [u8; Piece::COUNT] = [0, 1, 1, 2, 4, 0];
+
 impl Piece {
     /// All piece types.
     pub const ALL: [Piece; 6] = [
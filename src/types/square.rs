@@ -285,6 +285,22 @@ impl Square {
         }
     }
 
+    /// Get the square `file_delta` files and `rank_delta` ranks away (if valid).
+    #[inline(always)]
+    pub const fn offset(self, file_delta: i8, rank_delta: i8) -> Option<Square> {
+        let file = (self.0 & 7) as i16 + file_delta as i16;
+        let rank = (self.0 >> 3) as i16 + rank_delta as i16;
+        if file < 0 || file > 7 || rank < 0 || rank > 7 {
+            return None;
+        }
+        unsafe {
+            Some(Square::from_file_rank(
+                File::from_index_unchecked(file as u8),
+                Rank::from_index_unchecked(rank as u8),
+            ))
+        }
+    }
+
     /// Parse a square from algebraic notation (e.g., "e4").
     pub fn from_algebraic(s: &str) -> Option<Square> {
         let bytes = s.as_bytes();
@@ -303,6 +319,28 @@ impl Square {
     }
 }
 
+/// Error returned when parsing a [`Square`] from a string fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseSquareError;
+
+impl fmt::Display for ParseSquareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid square")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseSquareError {}
+
+impl core::str::FromStr for Square {
+    type Err = ParseSquareError;
+
+    /// Parse algebraic notation such as `e4`.
+    fn from_str(s: &str) -> Result<Square, ParseSquareError> {
+        Square::from_algebraic(s).ok_or(ParseSquareError)
+    }
+}
+
 impl fmt::Debug for Square {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let [file, rank] = self.to_algebraic();
@@ -342,6 +380,8 @@ mod tests {
         assert_eq!(Square::from_algebraic("a1"), Some(Square::A1));
         assert_eq!(Square::from_algebraic("h8"), Some(Square::H8));
         assert_eq!(Square::from_algebraic("i9"), None);
+        assert_eq!("e4".parse(), Ok(Square::E4));
+        assert_eq!("e44".parse::<Square>(), Err(ParseSquareError));
     }
 
     #[test]
@@ -362,4 +402,14 @@ mod tests {
         assert_eq!(Square::H4.east(), None);
         assert_eq!(Square::A4.west(), None);
     }
+
+    #[test]
+    fn test_square_offset() {
+        assert_eq!(Square::G1.offset(-1, 2), Some(Square::F3));
+        assert_eq!(Square::E4.offset(0, 0), Some(Square::E4));
+        assert_eq!(Square::A1.offset(7, 7), Some(Square::H8));
+        assert_eq!(Square::H4.offset(1, 0), None);
+        assert_eq!(Square::A8.offset(0, 1), None);
+        assert_eq!(Square::H8.offset(i8::MAX, i8::MIN), None);
+    }
 }
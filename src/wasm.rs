@@ -0,0 +1,116 @@
+//! `wasm-bindgen` wrapper over [`Board`] for browser front ends.
+//!
+//! Build a loadable module with
+//! `cargo rustc --lib --release --features wasm --target wasm32-unknown-unknown --crate-type cdylib`
+//! and run `wasm-bindgen` on the output.
+
+use wasm_bindgen::prelude::*;
+use crate::board::{Board, FenError};
+use crate::movegen::GameStatus;
+
+/// A position exposed to JavaScript as `Board`. Moves cross the boundary as
+/// UCI strings.
+#[wasm_bindgen(js_name = Board)]
+#[derive(Clone, Copy, Debug)]
+pub struct WasmBoard {
+    board: Board,
+}
+
+#[wasm_bindgen(js_class = Board)]
+impl WasmBoard {
+    /// Start from the initial position.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmBoard {
+        WasmBoard { board: Board::startpos() }
+    }
+
+    /// Parse a FEN string, throwing on input that
+    /// [`Board::from_fen_strict`] rejects.
+    #[wasm_bindgen(js_name = fromFen)]
+    pub fn from_fen(fen: &str) -> Result<WasmBoard, JsError> {
+        WasmBoard::parse_fen(fen).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Legal moves in UCI notation.
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self) -> Vec<String> {
+        self.board.generate_moves().iter().map(|m| self.board.move_to_uci(m)).collect()
+    }
+
+    /// Play a UCI move. Returns `false` and leaves the board untouched if
+    /// the move is malformed or illegal.
+    #[wasm_bindgen(js_name = makeUci)]
+    pub fn make_uci(&mut self, mv: &str) -> bool {
+        match self.board.parse_uci(mv) {
+            Some(mv) => self.board.try_make_move(mv).is_ok(),
+            None => false,
+        }
+    }
+
+    /// The position as FEN.
+    pub fn fen(&self) -> String {
+        self.board.to_fen()
+    }
+
+    /// Game status: `ongoing`, `checkmate`, `stalemate`, `fifty-move` or
+    /// `insufficient-material`.
+    pub fn status(&self) -> String {
+        match self.board.status() {
+            GameStatus::Ongoing => "ongoing",
+            GameStatus::Checkmate => "checkmate",
+            GameStatus::Stalemate => "stalemate",
+            GameStatus::DrawFiftyMove => "fifty-move",
+            GameStatus::DrawInsufficientMaterial => "insufficient-material",
+        }
+        .to_string()
+    }
+}
+
+impl Default for WasmBoard {
+    fn default() -> WasmBoard {
+        WasmBoard::new()
+    }
+}
+
+impl WasmBoard {
+    /// [`WasmBoard::from_fen`] without the JS error conversion, which only
+    /// works on wasm targets.
+    fn parse_fen(fen: &str) -> Result<WasmBoard, FenError> {
+        Board::from_fen_strict(fen).map(|board| WasmBoard { board })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wasm_board() {
+        let mut board = WasmBoard::new();
+        assert_eq!(board.legal_moves().len(), 20);
+        assert!(!board.make_uci("e2e5"));
+        assert!(!board.make_uci("junk"));
+        for mv in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            assert!(board.make_uci(mv), "{mv}");
+        }
+        assert_eq!(board.status(), "checkmate");
+        assert!(board.legal_moves().is_empty());
+        assert_eq!(board.fen(), "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3");
+    }
+
+    #[test]
+    fn test_wasm_board_chess960_castling() {
+        let board = WasmBoard::from_fen("4k3/8/8/8/8/8/8/1RK4R w HB - 0 1").unwrap();
+        let moves = board.legal_moves();
+        assert!(moves.contains(&"c1b1".to_string()));
+        for mv in moves {
+            let mut copy = WasmBoard::from_fen(&board.fen()).unwrap();
+            assert!(copy.make_uci(&mv), "{mv}");
+        }
+    }
+
+    #[test]
+    fn test_wasm_board_rejects_kingless_fen() {
+        assert_eq!(WasmBoard::parse_fen("4k3/8/8/8/8/8/8/8 w - - 0 1").unwrap_err(), FenError::BadKingCount);
+    }
+}